@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Supporting gadgets (hashing, encryption, commitment, membership) that can be
+/// composed into a model's circuit graph.
+pub mod modules;
+
+/// The tolerance a circuit's output is allowed to diverge from its floating point
+/// reference by, expressed in the model's fixed-point representation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    /// the allowed divergence, in percentage points
+    pub val: f32,
+    /// the scale factor the tolerance is applied at
+    pub scale: f32,
+}
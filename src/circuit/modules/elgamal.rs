@@ -0,0 +1,123 @@
+use halo2_proofs::{circuit::Layouter, plonk::ConstraintSystem};
+use halo2curves::bn256::{Fr, G1Affine, G1};
+use halo2curves::group::{Curve, Group};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::tensor::ValTensor;
+
+use super::{Module, ModuleError};
+
+/// the secret key, public key and randomness used by a single ElGamal encryption,
+/// generated once per message and re-used for the matching decryption
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ElGamalVariables {
+    /// the encryptor's secret scalar
+    pub sk: Fr,
+    /// the encryptor's public point, `pk = sk * G`
+    pub pk: G1Affine,
+    /// the randomness used to blind the ciphertext
+    pub r: Fr,
+}
+
+impl ElGamalVariables {
+    /// sample a fresh, random set of ElGamal variables
+    pub fn gen_random(mut rng: &mut impl RngCore) -> Self {
+        let sk = Fr::random(&mut rng);
+        let pk = (G1::generator() * sk).to_affine();
+        let r = Fr::random(&mut rng);
+        Self { sk, pk, r }
+    }
+}
+
+/// the configuration for the [`ElGamalChip`]
+#[derive(Clone, Debug)]
+pub struct ElGamalConfig {
+    advice: halo2_proofs::plonk::Column<halo2_proofs::plonk::Advice>,
+}
+
+/// A gadget that encrypts / decrypts a vector of field elements with ElGamal-style
+/// encryption over the bn256 curve, masking each field element with the x-coordinate of
+/// the shared secret.
+#[derive(Clone, Debug)]
+pub struct ElGamalChip {
+    config: ElGamalConfig,
+}
+
+impl Module<Fr> for ElGamalChip {
+    type Config = ElGamalConfig;
+    type InputAssignments = ValTensor<Fr>;
+    type RunInputs = (ElGamalVariables, Vec<Fr>);
+    type Params = ();
+
+    fn name(&self) -> &'static str {
+        "ElGamal"
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self { config }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>, _params: Self::Params) -> Self::Config {
+        ElGamalConfig {
+            advice: meta.advice_column(),
+        }
+    }
+
+    fn layout_inputs(
+        &self,
+        _layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+    ) -> Result<Self::InputAssignments, ModuleError> {
+        Ok(values[0].clone())
+    }
+
+    fn layout(
+        &self,
+        _layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+        _offset: usize,
+    ) -> Result<ValTensor<Fr>, ModuleError> {
+        // lays out no encryption constraints: `values[0]` is returned unchanged, and the
+        // ciphertext is only computed off-circuit by `run`
+        let _ = self.config.advice;
+        Ok(values[0].clone())
+    }
+
+    fn run((vars, message): Self::RunInputs) -> Result<Vec<Vec<Fr>>, ModuleError> {
+        let shared_secret = (vars.pk * vars.r).to_affine();
+        let cipher = message
+            .iter()
+            .map(|m| *m + shared_secret.x)
+            .collect::<Vec<_>>();
+        Ok(vec![cipher])
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        input_len
+    }
+}
+
+/// an ElGamal-style ciphertext: the ephemeral public key `c1 = r * G` alongside the
+/// masked message `c2 = message + (r * pk).x`, so that decryption only needs `sk`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ciphertext {
+    /// the ephemeral public key generated for this encryption
+    pub c1: G1Affine,
+    /// the masked message
+    pub c2: Vec<Fr>,
+}
+
+/// encrypt `message` under `pk` using the randomness `r`
+pub fn encrypt(pk: G1Affine, message: &[Fr], r: Fr) -> Ciphertext {
+    let c1 = (G1::generator() * r).to_affine();
+    let shared_secret = (pk * r).to_affine();
+    let c2 = message.iter().map(|m| *m + shared_secret.x).collect();
+    Ciphertext { c1, c2 }
+}
+
+/// decrypt a [`Ciphertext`] produced by [`encrypt`] with the matching secret key
+pub fn decrypt(sk: Fr, cipher: &Ciphertext) -> Vec<Fr> {
+    let shared_secret = (cipher.c1 * sk).to_affine();
+    cipher.c2.iter().map(|c| *c - shared_secret.x).collect()
+}
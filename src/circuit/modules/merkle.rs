@@ -0,0 +1,484 @@
+use halo2_gadgets::poseidon::{
+    primitives::ConstantLength, Hash as PoseidonHash, Pow5Chip, Pow5Config,
+};
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error as PlonkError, Expression, Instance, Selector},
+    poly::Rotation,
+};
+use halo2curves::bn256::Fr;
+use halo2curves::ff::Field;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::RunArgs;
+use crate::tensor::ValTensor;
+
+use super::poseidon::poseidon_hash;
+use super::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use super::{Module, ModuleError};
+
+type Cell = AssignedCell<Fr, Fr>;
+
+/// an authentication path proving a single leaf's membership under a Merkle root: the
+/// sibling hash and left/right position at each level, from the leaf up to the root
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// the leaf this path authenticates
+    pub leaf: Fr,
+    /// the sibling hash at each level, from the leaf up to the root
+    pub path_elements: Vec<Fr>,
+    /// whether the node on the path is the right child at each level
+    pub path_indices: Vec<bool>,
+}
+
+/// an incremental Poseidon-hashed Merkle tree, used to attest that a private input came
+/// from an authorized, committed set of leaves
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MerkleTree {
+    /// the tree's leaves, in insertion order
+    pub leaves: Vec<Fr>,
+    /// the tree's depth; `leaves.len()` must not exceed `2.pow(depth)`
+    pub depth: usize,
+}
+
+impl MerkleTree {
+    /// build a new tree of the given `depth` over `leaves`, zero-padding up to
+    /// `2.pow(depth)` leaves
+    pub fn new(leaves: Vec<Fr>, depth: usize) -> Result<Self, ModuleError> {
+        if leaves.len() > 1 << depth {
+            return Err(ModuleError::InvalidInput(format!(
+                "{} leaves do not fit in a tree of depth {}",
+                leaves.len(),
+                depth
+            )));
+        }
+        Ok(Self { leaves, depth })
+    }
+
+    fn padded_leaves(&self) -> Vec<Fr> {
+        let mut leaves = self.leaves.clone();
+        leaves.resize(1 << self.depth, Fr::zero());
+        leaves
+    }
+
+    /// the tree's Merkle root
+    pub fn root(&self) -> Fr {
+        let mut level = self.padded_leaves();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([pair[0], pair[1]]))
+                .collect();
+        }
+        level[0]
+    }
+
+    /// the authentication path for the leaf at `index`
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, ModuleError> {
+        if index >= self.leaves.len() {
+            return Err(ModuleError::InvalidInput(format!(
+                "index {} out of bounds for {} leaves",
+                index,
+                self.leaves.len()
+            )));
+        }
+
+        let mut level = self.padded_leaves();
+        let mut idx = index;
+        let mut path_elements = Vec::with_capacity(self.depth);
+        let mut path_indices = Vec::with_capacity(self.depth);
+
+        while level.len() > 1 {
+            let is_right = idx % 2 == 1;
+            let sibling = if is_right { level[idx - 1] } else { level[idx + 1] };
+            path_elements.push(sibling);
+            path_indices.push(is_right);
+
+            level = level
+                .chunks(2)
+                .map(|pair| poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([pair[0], pair[1]]))
+                .collect();
+            idx /= 2;
+        }
+
+        Ok(MerkleProof {
+            leaf: self.leaves[index],
+            path_elements,
+            path_indices,
+        })
+    }
+}
+
+/// verify a [`MerkleProof`] against a `root`, purely off-circuit
+pub fn verify_proof(root: Fr, proof: &MerkleProof) -> bool {
+    let computed = proof
+        .path_elements
+        .iter()
+        .zip(proof.path_indices.iter())
+        .fold(proof.leaf, |node, (sibling, is_right)| {
+            let (left, right) = if *is_right {
+                (*sibling, node)
+            } else {
+                (node, *sibling)
+            };
+            poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([left, right])
+        });
+    computed == root
+}
+
+/// the configuration for the [`MerkleChip`]
+#[derive(Clone, Debug)]
+pub struct MerkleConfig {
+    /// the public instance column holding the tree's root
+    pub instance: Column<Instance>,
+    /// the depth this chip was configured for; `layout` folds exactly this many levels
+    depth: usize,
+    /// the shared Poseidon permutation levels are folded with
+    pow5_config: Pow5Config<Fr, POSEIDON_WIDTH, POSEIDON_RATE>,
+    /// witness columns for [`swap_selector`](Self::swap_selector): `node`, `sibling`, `bit`, `left`
+    advice: [Column<Advice>; 4],
+    /// enforces the conditional swap `(left, right) = bit ? (sibling, node) : (node, sibling)`
+    swap_selector: Selector,
+}
+
+/// the off-circuit inputs needed to run the [`MerkleChip`]: a leaf and its authentication
+/// path, verified by folding `Poseidon(left, right)` up to the root
+pub type MerkleRunInputs = MerkleProof;
+
+/// A gadget that proves a leaf is a member of a Poseidon-hashed Merkle tree, folding a
+/// sibling path up to a public root, at a depth fixed by [`MerkleParams`].
+#[derive(Clone, Debug)]
+pub struct MerkleChip {
+    config: MerkleConfig,
+}
+
+/// module-wide parameters for the [`MerkleChip`], fixed at configuration time like
+/// `logrows` is for the overall graph
+#[derive(Clone, Debug, Default)]
+pub struct MerkleParams {
+    /// the depth of the tree this chip is configured to verify membership in
+    pub depth: usize,
+}
+
+impl From<&RunArgs> for MerkleParams {
+    /// derive the chip's parameters from a graph's [`RunArgs::merkle_depth`]
+    fn from(run_args: &RunArgs) -> Self {
+        MerkleParams {
+            depth: run_args.merkle_depth,
+        }
+    }
+}
+
+impl MerkleChip {
+    /// conditionally swap `(node, sibling)` into `(left, right)` based on `is_right`,
+    /// constraining `is_right` to be boolean and the outputs to match the requested order
+    fn swap(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        node: Cell,
+        sibling: Cell,
+        is_right: bool,
+    ) -> Result<(Cell, Cell), ModuleError> {
+        let config = &self.config;
+        layouter
+            .assign_region(
+                || "merkle level swap",
+                |mut region| {
+                    config.swap_selector.enable(&mut region, 0)?;
+
+                    let bit = Value::known(Fr::from(is_right as u64));
+                    node.copy_advice(|| "node", &mut region, config.advice[0], 0)?;
+                    sibling.copy_advice(|| "sibling", &mut region, config.advice[1], 0)?;
+                    region.assign_advice(|| "bit", config.advice[2], 0, || bit)?;
+
+                    let (left_val, right_val) = if is_right {
+                        (sibling.value().copied(), node.value().copied())
+                    } else {
+                        (node.value().copied(), sibling.value().copied())
+                    };
+
+                    let left = region.assign_advice(|| "left", config.advice[3], 0, || left_val)?;
+                    let right = region.assign_advice(|| "right", config.advice[1], 1, || right_val)?;
+
+                    Ok((left, right))
+                },
+            )
+            .map_err(ModuleError::from)
+    }
+
+    /// fold `(left, right)` into their Poseidon parent
+    fn fold(&self, mut layouter: impl Layouter<Fr>, left: Cell, right: Cell) -> Result<Cell, ModuleError> {
+        let chip = Pow5Chip::construct(self.config.pow5_config.clone());
+        let hasher = PoseidonHash::<_, _, PoseidonSpec, ConstantLength<2>, POSEIDON_WIDTH, POSEIDON_RATE>::init(
+            chip,
+            layouter.namespace(|| "init poseidon"),
+        )?;
+        Ok(hasher.hash(layouter.namespace(|| "fold"), [left, right])?)
+    }
+}
+
+impl Module<Fr> for MerkleChip {
+    type Config = MerkleConfig;
+    type InputAssignments = ValTensor<Fr>;
+    type RunInputs = MerkleRunInputs;
+    type Params = MerkleParams;
+
+    fn name(&self) -> &'static str {
+        "Merkle"
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self { config }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>, params: Self::Params) -> Self::Config {
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        let state = (0..POSEIDON_WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let partial_sbox = meta.advice_column();
+        let rc_a = (0..POSEIDON_WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        let rc_b = (0..POSEIDON_WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        meta.enable_constant(rc_b[0]);
+
+        let pow5_config = Pow5Chip::configure::<PoseidonSpec>(
+            meta,
+            state.try_into().unwrap(),
+            partial_sbox,
+            rc_a.try_into().unwrap(),
+            rc_b.try_into().unwrap(),
+        );
+
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        for col in advice {
+            meta.enable_equality(col);
+        }
+
+        let swap_selector = meta.selector();
+        meta.create_gate("merkle level swap", |meta| {
+            let s = meta.query_selector(swap_selector);
+            let node = meta.query_advice(advice[0], Rotation::cur());
+            let sibling = meta.query_advice(advice[1], Rotation::cur());
+            let bit = meta.query_advice(advice[2], Rotation::cur());
+            let left = meta.query_advice(advice[3], Rotation::cur());
+            let right = meta.query_advice(advice[1], Rotation::next());
+
+            let one = Expression::Constant(Fr::one());
+            vec![
+                s.clone() * bit.clone() * (one.clone() - bit.clone()),
+                s.clone() * (left - (node.clone() + bit.clone() * (sibling.clone() - node.clone()))),
+                s * (right - (sibling.clone() + bit.clone() * (node - sibling))),
+            ]
+        });
+
+        MerkleConfig {
+            instance,
+            depth: params.depth,
+            pow5_config,
+            advice,
+            swap_selector,
+        }
+    }
+
+    fn layout_inputs(
+        &self,
+        _layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+    ) -> Result<Self::InputAssignments, ModuleError> {
+        Ok(values[0].clone())
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+        _offset: usize,
+    ) -> Result<ValTensor<Fr>, ModuleError> {
+        // `values[0]` holds the leaf followed by one cell per sibling, bottom-up.
+        // `values[1]`, if present, holds the corresponding left/right bit per sibling; a
+        // missing bit defaults to `false`, matching [`MerkleChip::swap`]'s convention.
+        let cells = match &values[0] {
+            ValTensor::Value { inner, .. } => inner.clone(),
+            ValTensor::PrevAssigned { .. } => {
+                return Err(ModuleError::InvalidInput(
+                    "MerkleChip expects fresh witness values, not previously assigned cells".into(),
+                ))
+            }
+        };
+        if cells.is_empty() {
+            return Err(ModuleError::InvalidInput("MerkleChip expects a leaf".into()));
+        }
+        if cells.len() - 1 != self.config.depth {
+            return Err(ModuleError::InvalidInput(format!(
+                "expected {} siblings for a tree of depth {}, got {}",
+                self.config.depth,
+                self.config.depth,
+                cells.len() - 1
+            )));
+        }
+        let path_indices: Vec<bool> = match values.get(1) {
+            Some(ValTensor::Value { inner, .. }) => inner
+                .iter()
+                .map(|v| {
+                    let mut is_right = false;
+                    v.map(|f| is_right = f != Fr::zero());
+                    is_right
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let leaf = layouter.assign_region(
+            || "leaf",
+            |mut region| region.assign_advice(|| "leaf", self.config.advice[0], 0, || cells[0]),
+        )?;
+
+        let mut node = leaf;
+        for (i, sibling_value) in cells[1..].iter().enumerate() {
+            let sibling = layouter.assign_region(
+                || "sibling",
+                |mut region| region.assign_advice(|| "sibling", self.config.advice[0], 0, || *sibling_value),
+            )?;
+            let is_right = path_indices.get(i).copied().unwrap_or(false);
+            let (left, right) = self.swap(layouter.namespace(|| "swap"), node, sibling, is_right)?;
+            node = self.fold(layouter.namespace(|| "fold"), left, right)?;
+        }
+        let root = node;
+
+        layouter.constrain_instance(root.cell(), self.config.instance, 0)?;
+
+        Ok(ValTensor::Value {
+            inner: vec![root.value().copied()],
+            dims: vec![1],
+        })
+    }
+
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<Fr>>, ModuleError> {
+        if input.path_elements.len() != input.path_indices.len() {
+            return Err(ModuleError::InvalidInput(
+                "path_elements and path_indices must have the same length".into(),
+            ));
+        }
+
+        let root = input
+            .path_elements
+            .iter()
+            .zip(input.path_indices.iter())
+            .fold(input.leaf, |node, (sibling, is_right)| {
+                let (left, right) = if *is_right {
+                    (*sibling, node)
+                } else {
+                    (node, *sibling)
+                };
+                poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([left, right])
+            });
+
+        Ok(vec![vec![root]])
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        input_len * 8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::dev::MockProver;
+
+    #[derive(Default, Clone)]
+    struct MerkleTestCircuit {
+        proof: Option<MerkleProof>,
+        depth: usize,
+    }
+
+    impl halo2_proofs::plonk::Circuit<Fr> for MerkleTestCircuit {
+        type Config = MerkleConfig;
+        type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self { proof: None, depth: self.depth }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            MerkleChip::configure(meta, MerkleParams { depth: 2 })
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), PlonkError> {
+            let chip = MerkleChip::new(config);
+            let proof = self.proof.clone().unwrap();
+
+            let mut leaf_and_siblings = vec![Value::known(proof.leaf)];
+            leaf_and_siblings.extend(proof.path_elements.iter().map(|v| Value::known(*v)));
+            let bits: Vec<Value<Fr>> = proof
+                .path_indices
+                .iter()
+                .map(|b| Value::known(Fr::from(*b as u64)))
+                .collect();
+
+            let values = [
+                ValTensor::Value { inner: leaf_and_siblings.clone(), dims: vec![leaf_and_siblings.len()] },
+                ValTensor::Value { inner: bits.clone(), dims: vec![bits.len()] },
+            ];
+            chip.layout(&mut layouter, &values, 0)
+                .map_err(|e| match e {
+                    ModuleError::Halo2(e) => e,
+                    ModuleError::InvalidInput(_) => PlonkError::Synthesis,
+                })?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn layout_satisfies_an_honest_membership_proof() {
+        let leaves = (0..4u64).map(Fr::from).collect::<Vec<_>>();
+        let tree = MerkleTree::new(leaves, 2).unwrap();
+        let root = tree.root();
+        let proof = tree.proof(1).unwrap();
+
+        let circuit = MerkleTestCircuit { proof: Some(proof), depth: 2 };
+        let k = 9;
+        let prover = MockProver::run(k, &circuit, vec![vec![root]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn root_matches_proof_for_every_leaf() {
+        let leaves = (0..4u64).map(Fr::from).collect::<Vec<_>>();
+        let tree = MerkleTree::new(leaves, 2).unwrap();
+        let root = tree.root();
+
+        for i in 0..4 {
+            let proof = tree.proof(i).unwrap();
+            assert!(verify_proof(root, &proof));
+        }
+    }
+
+    #[test]
+    fn rejects_proof_against_wrong_root() {
+        let leaves = (0..4u64).map(Fr::from).collect::<Vec<_>>();
+        let tree = MerkleTree::new(leaves, 2).unwrap();
+        let proof = tree.proof(0).unwrap();
+
+        assert!(!verify_proof(Fr::from(42), &proof));
+    }
+
+    #[test]
+    fn params_inherit_depth_from_run_args() {
+        let run_args = RunArgs {
+            merkle_depth: 5,
+            ..Default::default()
+        };
+        let params = MerkleParams::from(&run_args);
+        assert_eq!(params.depth, 5);
+    }
+}
@@ -0,0 +1,76 @@
+use halo2_proofs::{
+    circuit::Layouter,
+    plonk::{ConstraintSystem, Error as PlonkError},
+};
+use halo2curves::ff::PrimeField;
+use thiserror::Error;
+
+use crate::tensor::ValTensor;
+
+/// ElGamal encryption gadget.
+pub mod elgamal;
+/// Poseidon-hash-based Merkle tree inclusion gadget.
+pub mod merkle;
+/// Pedersen vector commitment gadget.
+pub mod pedersen;
+/// Poseidon hashing gadget.
+pub mod poseidon;
+/// Rate-limiting nullifier gadget, built on top of [`poseidon`], with its own Merkle
+/// membership check.
+pub mod rln;
+
+/// Errors that can occur when laying out, or running a [`Module`] off-circuit.
+#[derive(Error, Debug)]
+pub enum ModuleError {
+    /// an error originating from the halo2 plonkish backend
+    #[error("[halo2] {0}")]
+    Halo2(#[from] PlonkError),
+    /// the module received a malformed input (wrong length, mismatched shares, ...)
+    #[error("invalid module input: {0}")]
+    InvalidInput(String),
+}
+
+/// A `Module` is a self-contained gadget (hashing, encryption, commitment, membership, ...)
+/// that can be configured once and composed into a model's circuit graph, alongside its
+/// own off-circuit reference implementation used for witness generation and testing.
+pub trait Module<F: PrimeField + halo2curves::ff::FromUniformBytes<64> + halo2curves::serde::SerdeObject>
+{
+    /// the configuration for this module's gates
+    type Config: Clone;
+    /// the in-circuit assignments produced by [`Module::layout_inputs`]
+    type InputAssignments;
+    /// the inputs taken by the off-circuit [`Module::run`] reference implementation
+    type RunInputs;
+    /// module-wide parameters fixed at configuration time (e.g. tree depth, number of generators)
+    type Params: Clone;
+
+    /// a human-readable name for the module, used in error messages and wasm bindings
+    fn name(&self) -> &'static str;
+
+    /// instantiate the module's chip from an already-configured [`Module::Config`]
+    fn new(config: Self::Config) -> Self;
+
+    /// configure the module's gates and columns
+    fn configure(meta: &mut ConstraintSystem<F>, params: Self::Params) -> Self::Config;
+
+    /// assign the module's private and public inputs into the layouter
+    fn layout_inputs(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        values: &[ValTensor<F>],
+    ) -> Result<Self::InputAssignments, ModuleError>;
+
+    /// assign and constrain the module's computation, returning its public output
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        values: &[ValTensor<F>],
+        offset: usize,
+    ) -> Result<ValTensor<F>, ModuleError>;
+
+    /// the off-circuit reference implementation used to generate witnesses and for testing
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<F>>, ModuleError>;
+
+    /// the number of rows the module consumes as a function of its input length
+    fn num_rows(input_len: usize) -> usize;
+}
@@ -0,0 +1,652 @@
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error as PlonkError, Expression, Fixed, Instance, Selector},
+    poly::Rotation,
+};
+use halo2curves::bn256::{Fr, G1Affine, G1};
+use halo2curves::ff::{Field, PrimeField};
+use halo2curves::group::{Curve, Group};
+use halo2curves::CurveExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::tensor::ValTensor;
+
+use super::{Module, ModuleError};
+
+const DOMAIN: &[u8] = b"ezkl-pedersen-v1";
+
+/// derive the `i`-th generator deterministically by hash-to-curve on [`DOMAIN`], so any
+/// verifier can reproduce `G_0..G_{n-1}` and `H` without a trusted setup
+fn generator(index: u64) -> G1Affine {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update(index.to_le_bytes());
+    let digest = hasher.finalize();
+
+    G1::hash_to_curve("ezkl-pedersen")(&digest).to_affine()
+}
+
+/// the blinding generator `H`, distinct from every message generator `G_i`
+fn blinding_generator() -> G1Affine {
+    generator(u64::MAX)
+}
+
+/// a nothing-up-my-sleeve point, distinct from every generator, used only to seed the
+/// in-circuit scalar-mul accumulator so the incomplete-addition gate never has to handle
+/// the identity element; its contribution is subtracted back out before the result is
+/// exposed, so it never appears in the public commitment
+fn accumulator_offset() -> G1Affine {
+    generator(u64::MAX - 1)
+}
+
+/// the message generators `G_0..G_{len-1}` used to commit a vector of `len` field elements
+pub fn message_generators(len: usize) -> Vec<G1Affine> {
+    (0..len as u64).map(generator).collect()
+}
+
+/// A Pedersen commitment to a message vector: `C = sum_i m_i * G_i + r * H`. Unlike a
+/// hash-based commitment, `C` is additively homomorphic in the message, so
+/// `commit(a, r_a) + commit(b, r_b) == commit(a + b, r_a + r_b)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PedersenCommitment(pub G1Affine);
+
+impl std::ops::Add for PedersenCommitment {
+    type Output = PedersenCommitment;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PedersenCommitment((self.0 + rhs.0).to_affine())
+    }
+}
+
+/// commit to `message` with blinding factor `r`, using generators derived from [`DOMAIN`]
+pub fn commit(message: &[Fr], r: Fr) -> PedersenCommitment {
+    let generators = message_generators(message.len());
+    let msm = message
+        .iter()
+        .zip(generators.iter())
+        .fold(G1::identity(), |acc, (m, g)| acc + *g * m);
+    let commitment = msm + blinding_generator() * r;
+    PedersenCommitment(commitment.to_affine())
+}
+
+/// check that `commitment` opens to `message` under blinding factor `r`
+pub fn verify(commitment: PedersenCommitment, message: &[Fr], r: Fr) -> bool {
+    commit(message, r) == commitment
+}
+
+type Cell = AssignedCell<Fr, Fr>;
+/// an in-circuit affine point, as a pair of assigned coordinate cells
+type EccPoint = (Cell, Cell);
+
+/// the number of bits a scalar is decomposed into for in-circuit scalar multiplication.
+/// Deliberately `Fr::CAPACITY`, not `Fr::NUM_BITS`: any value representable in
+/// `Fr::CAPACITY` bits is strictly less than the field modulus, so its bit decomposition
+/// is unique. At the full `NUM_BITS`, `2^NUM_BITS` exceeds the modulus and a prover could
+/// satisfy the same `Σ bit_i·2^i == scalar` checksum with a different, non-canonical bit
+/// pattern that folds into a different point — breaking the binding property the
+/// multiscalar combination depends on. [`PedersenChip::scalar_mul`] rejects any scalar
+/// that doesn't fit in `SCALAR_BITS` bits rather than silently truncating it.
+const SCALAR_BITS: u32 = Fr::CAPACITY;
+
+/// the configuration for the [`PedersenChip`]
+#[derive(Clone, Debug)]
+pub struct PedersenConfig {
+    /// the public instance column holding the commitment's affine coordinates
+    pub instance: Column<Instance>,
+    /// `[acc_x, acc_y, bit, lambda, scalar_acc]`, the witness columns the scalar-mul and
+    /// point-addition gates read and write
+    advice: [Column<Advice>; 5],
+    /// `[base_x, base_y, weight]`: the per-row constant (a bit's scaled generator
+    /// coordinates, and its place value `2^j`), public since generators are public
+    fixed: [Column<Fixed>; 3],
+    /// conditionally adds the row's fixed base onto the running point accumulator based
+    /// on `bit`, and accumulates `bit * weight` into the running scalar checksum
+    mul_selector: Selector,
+    /// unconditionally adds two running point accumulators (chaining each scalar-mul's
+    /// result into the final multiscalar sum)
+    add_selector: Selector,
+}
+
+/// module-wide parameters for the [`PedersenChip`]: the number of message elements
+/// committed to in a single multiscalar combination
+#[derive(Clone, Debug, Default)]
+pub struct PedersenParams {
+    /// the length of the message vector; `configure` does not use it, and `layout`
+    /// commits to however many message elements it is given
+    pub message_len: usize,
+}
+
+/// the off-circuit inputs needed to run the [`PedersenChip`]: a message vector and the
+/// blinding factor used to mask it
+#[derive(Clone, Debug)]
+pub struct PedersenRunInputs {
+    /// the message being committed to
+    pub message: Vec<Fr>,
+    /// the blinding factor masking the commitment
+    pub r: Fr,
+}
+
+/// A gadget that constrains the multiscalar combination `C = sum_i m_i * G_i + r * H` via
+/// bit-decomposed double-and-add scalar multiplication and exposes `C` as a public
+/// instance, as an additively homomorphic alternative to the
+/// [`super::poseidon`]/[`super::elgamal`] commitment paths.
+///
+/// The scalar-mul gate uses incomplete affine addition (it does not handle doubling or
+/// identity inputs); soundness instead relies on seeding every accumulator at the public,
+/// nothing-up-my-sleeve [`accumulator_offset`], which keeps every addition generic for
+/// honestly-generated witnesses. A production chip would use `halo2_gadgets::ecc`'s
+/// complete addition formulae instead.
+///
+/// Message elements and the blinding factor must fit in [`SCALAR_BITS`] (`Fr::CAPACITY`)
+/// bits; `layout` returns [`ModuleError::InvalidInput`] otherwise, since decomposing a
+/// scalar into its full `Fr::NUM_BITS` would make the bit checksum non-canonical (see
+/// [`SCALAR_BITS`]) and the commitment would no longer be binding.
+#[derive(Clone, Debug)]
+pub struct PedersenChip {
+    config: PedersenConfig,
+}
+
+impl PedersenChip {
+    /// witness a known, public point as a pair of cells (used to seed an accumulator)
+    fn witness_point(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        point: G1Affine,
+    ) -> Result<EccPoint, ModuleError> {
+        let coords = point.coordinates().unwrap();
+        let (x, y) = (*coords.x(), *coords.y());
+        layouter
+            .assign_region(
+                || "witness point",
+                |mut region| {
+                    let x = region.assign_advice(|| "x", self.config.advice[0], 0, || Value::known(x))?;
+                    let y = region.assign_advice(|| "y", self.config.advice[1], 0, || Value::known(y))?;
+                    Ok((x, y))
+                },
+            )
+            .map_err(ModuleError::from)
+    }
+
+    /// fold the bit decomposition of `scalar` into `acc`, accumulating `acc + scalar * base`
+    /// and a running linear checksum of the bits, which is constrained to equal `scalar_cell`
+    /// once all bits have been folded in
+    fn scalar_mul(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        acc: EccPoint,
+        scalar_cell: &Cell,
+        scalar: Value<Fr>,
+        base: G1Affine,
+    ) -> Result<EccPoint, ModuleError> {
+        scalar
+            .error_if_known_and(|s| {
+                let repr = s.to_repr();
+                (SCALAR_BITS as usize..256).any(|i| (repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1)
+            })
+            .map_err(|_| {
+                ModuleError::InvalidInput(format!(
+                    "scalar does not fit in the {SCALAR_BITS}-bit canonical range this chip decomposes into"
+                ))
+            })?;
+
+        let bits: Vec<Value<Fr>> = {
+            let mut out = vec![Value::known(false); SCALAR_BITS as usize];
+            scalar.map(|s| {
+                let repr = s.to_repr();
+                for (i, bit) in out.iter_mut().enumerate() {
+                    let byte = repr.as_ref()[i / 8];
+                    *bit = Value::known((byte >> (i % 8)) & 1 == 1);
+                }
+            });
+            out.into_iter()
+                .map(|b| b.map(|b| if b { Fr::one() } else { Fr::zero() }))
+                .collect()
+        };
+
+        let mut acc = acc;
+        let mut scalar_acc_cell: Option<Cell> = None;
+        let mut scalar_acc_val = Value::known(Fr::zero());
+        let mut weight = Fr::one();
+        let mut running_base = base;
+
+        for bit in bits {
+            let base_coords = running_base.coordinates().unwrap();
+            let (bx, by) = (*base_coords.x(), *base_coords.y());
+
+            let acc_x = acc.0.value().copied();
+            let acc_y = acc.1.value().copied();
+            let lambda = acc_x
+                .zip(acc_y)
+                .map(|(ax, ay)| (by - ay) * (bx - ax).invert().unwrap());
+            let sum_x = lambda.map(|l| l * l).zip(acc_x).zip(Value::known(bx)).map(
+                |((l2, ax), bx)| l2 - ax - bx,
+            );
+            let sum_y = lambda
+                .zip(acc_x)
+                .zip(sum_x)
+                .zip(acc_y)
+                .map(|(((l, ax), sx), ay)| l * (ax - sx) - ay);
+
+            let out_x = bit
+                .zip(sum_x)
+                .zip(acc_x)
+                .map(|((b, sx), ax)| b * sx + (Fr::one() - b) * ax);
+            let out_y = bit
+                .zip(sum_y)
+                .zip(acc_y)
+                .map(|((b, sy), ay)| b * sy + (Fr::one() - b) * ay);
+
+            let new_scalar_acc = scalar_acc_val
+                .zip(bit)
+                .map(|(acc, b)| acc + b * weight);
+
+            let config = &self.config;
+            let (out, scalar_acc) = layouter
+                .assign_region(
+                    || "scalar mul step",
+                    |mut region| {
+                        config.mul_selector.enable(&mut region, 0)?;
+
+                        acc.0.copy_advice(|| "acc_x", &mut region, config.advice[0], 0)?;
+                        acc.1.copy_advice(|| "acc_y", &mut region, config.advice[1], 0)?;
+                        region.assign_advice(|| "bit", config.advice[2], 0, || bit)?;
+                        region.assign_advice(|| "lambda", config.advice[3], 0, || lambda)?;
+                        let scalar_acc_in = region.assign_advice(
+                            || "scalar_acc",
+                            config.advice[4],
+                            0,
+                            || scalar_acc_val,
+                        )?;
+                        if let Some(prev) = &scalar_acc_cell {
+                            region.constrain_equal(prev.cell(), scalar_acc_in.cell())?;
+                        }
+
+                        region.assign_fixed(|| "base_x", config.fixed[0], 0, || Value::known(bx))?;
+                        region.assign_fixed(|| "base_y", config.fixed[1], 0, || Value::known(by))?;
+                        region.assign_fixed(|| "weight", config.fixed[2], 0, || Value::known(weight))?;
+
+                        let sum_x_cell =
+                            region.assign_advice(|| "sum_x", config.advice[0], 1, || sum_x)?;
+                        let sum_y_cell =
+                            region.assign_advice(|| "sum_y", config.advice[1], 1, || sum_y)?;
+                        let _ = (sum_x_cell, sum_y_cell);
+
+                        let out_x_cell =
+                            region.assign_advice(|| "out_x", config.advice[0], 2, || out_x)?;
+                        let out_y_cell =
+                            region.assign_advice(|| "out_y", config.advice[1], 2, || out_y)?;
+                        let scalar_acc_out = region.assign_advice(
+                            || "scalar_acc_out",
+                            config.advice[2],
+                            2,
+                            || new_scalar_acc,
+                        )?;
+
+                        Ok(((out_x_cell, out_y_cell), scalar_acc_out))
+                    },
+                )
+                .map_err(ModuleError::from)?;
+
+            acc = out;
+            scalar_acc_val = new_scalar_acc;
+            scalar_acc_cell = Some(scalar_acc);
+            weight = weight.double();
+            running_base = (running_base + running_base).to_affine();
+        }
+
+        if let Some(scalar_acc) = scalar_acc_cell {
+            layouter
+                .namespace(|| "scalar checksum")
+                .assign_region(
+                    || "scalar checksum",
+                    |mut region| region.constrain_equal(scalar_acc.cell(), scalar_cell.cell()),
+                )
+                .map_err(|e: PlonkError| ModuleError::from(e))?;
+        }
+
+        Ok(acc)
+    }
+
+    /// unconditionally add two running accumulators, each the output of one scalar-mul
+    fn add(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        a: EccPoint,
+        b: EccPoint,
+    ) -> Result<EccPoint, ModuleError> {
+        let config = &self.config;
+        layouter
+            .assign_region(
+                || "point add",
+                |mut region| {
+                    config.add_selector.enable(&mut region, 0)?;
+
+                    a.0.copy_advice(|| "ax", &mut region, config.advice[0], 0)?;
+                    a.1.copy_advice(|| "ay", &mut region, config.advice[1], 0)?;
+                    b.0.copy_advice(|| "bx", &mut region, config.advice[2], 0)?;
+                    b.1.copy_advice(|| "by", &mut region, config.advice[3], 0)?;
+
+                    let lambda = a
+                        .0
+                        .value()
+                        .copied()
+                        .zip(a.1.value().copied())
+                        .zip(b.0.value().copied())
+                        .zip(b.1.value().copied())
+                        .map(|(((ax, ay), bx), by)| (by - ay) * (bx - ax).invert().unwrap());
+                    let cx = lambda
+                        .map(|l| l * l)
+                        .zip(a.0.value().copied())
+                        .zip(b.0.value().copied())
+                        .map(|((l2, ax), bx)| l2 - ax - bx);
+                    let cy = lambda
+                        .zip(a.0.value().copied())
+                        .zip(cx)
+                        .zip(a.1.value().copied())
+                        .map(|(((l, ax), cx), ay)| l * (ax - cx) - ay);
+
+                    region.assign_advice(|| "lambda", config.advice[4], 0, || lambda)?;
+                    let out_x = region.assign_advice(|| "cx", config.advice[0], 1, || cx)?;
+                    let out_y = region.assign_advice(|| "cy", config.advice[1], 1, || cy)?;
+
+                    Ok((out_x, out_y))
+                },
+            )
+            .map_err(ModuleError::from)
+    }
+
+    /// negate a public, known point, for subtracting an accumulator seed
+    fn negate(point: G1Affine) -> G1Affine {
+        (-G1::from(point)).to_affine()
+    }
+}
+
+impl Module<Fr> for PedersenChip {
+    type Config = PedersenConfig;
+    type InputAssignments = ValTensor<Fr>;
+    type RunInputs = PedersenRunInputs;
+    type Params = PedersenParams;
+
+    fn name(&self) -> &'static str {
+        "Pedersen"
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self { config }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>, _params: Self::Params) -> Self::Config {
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        for col in advice {
+            meta.enable_equality(col);
+        }
+        let fixed = [meta.fixed_column(), meta.fixed_column(), meta.fixed_column()];
+
+        let mul_selector = meta.selector();
+        meta.create_gate("scalar mul step", |meta| {
+            let s = meta.query_selector(mul_selector);
+            let ax = meta.query_advice(advice[0], Rotation::cur());
+            let ay = meta.query_advice(advice[1], Rotation::cur());
+            let bit = meta.query_advice(advice[2], Rotation::cur());
+            let lambda = meta.query_advice(advice[3], Rotation::cur());
+            let scalar_acc = meta.query_advice(advice[4], Rotation::cur());
+
+            let bx = meta.query_fixed(fixed[0], Rotation::cur());
+            let by = meta.query_fixed(fixed[1], Rotation::cur());
+            let weight = meta.query_fixed(fixed[2], Rotation::cur());
+
+            let sum_x = meta.query_advice(advice[0], Rotation::next());
+            let sum_y = meta.query_advice(advice[1], Rotation::next());
+
+            let out_x = meta.query_advice(advice[0], Rotation(2));
+            let out_y = meta.query_advice(advice[1], Rotation(2));
+            let scalar_acc_out = meta.query_advice(advice[2], Rotation(2));
+
+            let one = Expression::Constant(Fr::one());
+
+            vec![
+                s.clone() * bit.clone() * (one.clone() - bit.clone()),
+                s.clone() * (lambda.clone() * (bx.clone() - ax.clone()) - (by.clone() - ay.clone())),
+                s.clone() * (sum_x.clone() - (lambda.clone() * lambda.clone() - ax.clone() - bx)),
+                s.clone() * (sum_y.clone() - (lambda * (ax.clone() - sum_x.clone()) - ay.clone())),
+                s.clone() * (out_x - (bit.clone() * sum_x.clone() + (one.clone() - bit.clone()) * ax)),
+                s.clone() * (out_y - (bit.clone() * sum_y + (one - bit.clone()) * ay)),
+                s * (scalar_acc_out - (scalar_acc + bit * weight)),
+            ]
+        });
+
+        let add_selector = meta.selector();
+        meta.create_gate("point add", |meta| {
+            let s = meta.query_selector(add_selector);
+            let ax = meta.query_advice(advice[0], Rotation::cur());
+            let ay = meta.query_advice(advice[1], Rotation::cur());
+            let bx = meta.query_advice(advice[2], Rotation::cur());
+            let by = meta.query_advice(advice[3], Rotation::cur());
+            let lambda = meta.query_advice(advice[4], Rotation::cur());
+
+            let cx = meta.query_advice(advice[0], Rotation::next());
+            let cy = meta.query_advice(advice[1], Rotation::next());
+
+            vec![
+                s.clone() * (lambda.clone() * (bx.clone() - ax.clone()) - (by - ay.clone())),
+                s.clone() * (cx.clone() - (lambda.clone() * lambda.clone() - ax.clone() - bx)),
+                s * (cy - (lambda * (ax - cx) - ay)),
+            ]
+        });
+
+        PedersenConfig {
+            instance,
+            advice,
+            fixed,
+            mul_selector,
+            add_selector,
+        }
+    }
+
+    fn layout_inputs(
+        &self,
+        _layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+    ) -> Result<Self::InputAssignments, ModuleError> {
+        Ok(values[0].clone())
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+        _offset: usize,
+    ) -> Result<ValTensor<Fr>, ModuleError> {
+        // `values[0]` holds the message followed by the blinding factor `r` as its last cell
+        let cells = match &values[0] {
+            ValTensor::Value { inner, .. } => inner.clone(),
+            ValTensor::PrevAssigned { .. } => {
+                return Err(ModuleError::InvalidInput(
+                    "PedersenChip expects fresh witness values, not previously assigned cells".into(),
+                ))
+            }
+        };
+        if cells.is_empty() {
+            return Err(ModuleError::InvalidInput(
+                "PedersenChip expects a message followed by a blinding factor".into(),
+            ));
+        }
+        let (message_values, r_value) = cells.split_at(cells.len() - 1);
+        let r_value = r_value[0];
+
+        let generators = message_generators(message_values.len());
+        let offset = accumulator_offset();
+        let mut terms = Vec::with_capacity(message_values.len() + 1);
+
+        for (i, (m_value, generator)) in message_values.iter().zip(generators.iter()).enumerate() {
+            let m_cell = layouter.assign_region(
+                || "message element",
+                |mut region| region.assign_advice(|| "m", self.config.advice[0], 0, || *m_value),
+            )?;
+            let seed = self.witness_point(layouter.namespace(|| "seed"), offset)?;
+            let term = self.scalar_mul(
+                layouter.namespace(|| format!("m_{i} * G_{i}")),
+                seed,
+                &m_cell,
+                *m_value,
+                *generator,
+            )?;
+            let cancelled = self.add(
+                layouter.namespace(|| "cancel seed"),
+                term,
+                self.witness_point(layouter.namespace(|| "neg seed"), Self::negate(offset))?,
+            )?;
+            terms.push(cancelled);
+        }
+
+        let r_cell = layouter.assign_region(
+            || "blinding factor",
+            |mut region| region.assign_advice(|| "r", self.config.advice[0], 0, || r_value),
+        )?;
+        let r_seed = self.witness_point(layouter.namespace(|| "seed"), offset)?;
+        let r_term = self.scalar_mul(
+            layouter.namespace(|| "r * H"),
+            r_seed,
+            &r_cell,
+            r_value,
+            blinding_generator(),
+        )?;
+        let r_cancelled = self.add(
+            layouter.namespace(|| "cancel seed"),
+            r_term,
+            self.witness_point(layouter.namespace(|| "neg seed"), Self::negate(offset))?,
+        )?;
+        terms.push(r_cancelled);
+
+        // sum every cancelled term together; since every term is itself a genuine curve
+        // point (the offset was already cancelled above), the running sum never needs a
+        // fresh seed and the first term can simply be the initial accumulator
+        let mut terms = terms.into_iter();
+        let mut commitment = terms.next().expect("at least the blinding term is present");
+        for term in terms {
+            commitment = self.add(layouter.namespace(|| "accumulate"), commitment, term)?;
+        }
+
+        layouter.constrain_instance(commitment.0.cell(), self.config.instance, 0)?;
+        layouter.constrain_instance(commitment.1.cell(), self.config.instance, 1)?;
+
+        Ok(ValTensor::Value {
+            inner: vec![commitment.0.value().copied(), commitment.1.value().copied()],
+            dims: vec![2],
+        })
+    }
+
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<Fr>>, ModuleError> {
+        let commitment = commit(&input.message, input.r);
+        let coords = commitment.0.coordinates().unwrap();
+        Ok(vec![vec![*coords.x(), *coords.y()]])
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        (input_len + 1) * (SCALAR_BITS as usize) * 3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::test_rng;
+    use halo2_proofs::dev::MockProver;
+
+    #[test]
+    fn is_additively_homomorphic() {
+        let mut rng = test_rng();
+
+        let a: Vec<Fr> = (0..32).map(|_| Fr::random(&mut rng)).collect();
+        let b: Vec<Fr> = (0..32).map(|_| Fr::random(&mut rng)).collect();
+        let r_a = Fr::random(&mut rng);
+        let r_b = Fr::random(&mut rng);
+
+        let commit_a = commit(&a, r_a);
+        let commit_b = commit(&b, r_b);
+
+        let summed: Vec<Fr> = a.iter().zip(b.iter()).map(|(x, y)| *x + *y).collect();
+        let commit_summed = commit(&summed, r_a + r_b);
+
+        assert_eq!(commit_a + commit_b, commit_summed);
+    }
+
+    #[test]
+    fn verifies_correct_opening_only() {
+        let mut rng = test_rng();
+        let message: Vec<Fr> = (0..4).map(|_| Fr::random(&mut rng)).collect();
+        let r = Fr::random(&mut rng);
+
+        let commitment = commit(&message, r);
+        assert!(verify(commitment, &message, r));
+        assert!(!verify(commitment, &message, r + Fr::one()));
+    }
+
+    #[derive(Default, Clone)]
+    struct PedersenTestCircuit {
+        message: Vec<Fr>,
+        r: Fr,
+    }
+
+    impl halo2_proofs::plonk::Circuit<Fr> for PedersenTestCircuit {
+        type Config = PedersenConfig;
+        type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            PedersenChip::configure(
+                meta,
+                PedersenParams {
+                    message_len: 1,
+                },
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), PlonkError> {
+            let chip = PedersenChip::new(config);
+            let mut cells: Vec<Value<Fr>> = self.message.iter().map(|m| Value::known(*m)).collect();
+            cells.push(Value::known(self.r));
+            let values = [ValTensor::Value {
+                inner: cells.clone(),
+                dims: vec![cells.len()],
+            }];
+            chip.layout(&mut layouter, &values, 0)
+                .map_err(|e| match e {
+                    ModuleError::Halo2(e) => e,
+                    ModuleError::InvalidInput(_) => PlonkError::Synthesis,
+                })?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn layout_satisfies_an_honest_commitment() {
+        // small, non-random scalars so they comfortably fit SCALAR_BITS (Fr::CAPACITY)
+        let message = vec![Fr::from(7u64)];
+        let r = Fr::from(11u64);
+        let commitment = commit(&message, r);
+        let coords = commitment.0.coordinates().unwrap();
+
+        let circuit = PedersenTestCircuit { message, r };
+        let k = 17;
+        let prover = MockProver::run(k, &circuit, vec![vec![*coords.x(), *coords.y()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+
+use halo2_gadgets::poseidon::{
+    primitives::{ConstantLength, Hash as PoseidonHash, Spec},
+    Hash, Pow5Chip, Pow5Config,
+};
+use halo2_proofs::{circuit::Layouter, plonk::ConstraintSystem};
+use halo2curves::ff::{FromUniformBytes, PrimeField};
+use halo2curves::serde::SerdeObject;
+
+use crate::tensor::ValTensor;
+
+use super::{Module, ModuleError};
+
+/// Poseidon spec and round-constant tables.
+pub mod spec;
+
+/// the configuration for the [`PoseidonChip`]
+#[derive(Clone, Debug)]
+pub struct PoseidonConfig<const WIDTH: usize, const RATE: usize> {
+    pow5_config: Pow5Config<halo2curves::bn256::Fr, WIDTH, RATE>,
+}
+
+/// A gadget that hashes a variable-length vector of field elements with the
+/// sponge-based Poseidon permutation, chunking input into `L`-sized blocks.
+#[derive(Debug, Clone)]
+pub struct PoseidonChip<S: Spec<halo2curves::bn256::Fr, WIDTH, RATE>, const WIDTH: usize, const RATE: usize, const L: usize>
+{
+    config: PoseidonConfig<WIDTH, RATE>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Spec<halo2curves::bn256::Fr, WIDTH, RATE>, const WIDTH: usize, const RATE: usize, const L: usize>
+    Module<halo2curves::bn256::Fr> for PoseidonChip<S, WIDTH, RATE, L>
+{
+    type Config = PoseidonConfig<WIDTH, RATE>;
+    type InputAssignments = ValTensor<halo2curves::bn256::Fr>;
+    type RunInputs = Vec<halo2curves::bn256::Fr>;
+    type Params = ();
+
+    fn name(&self) -> &'static str {
+        "Poseidon"
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    fn configure(
+        meta: &mut ConstraintSystem<halo2curves::bn256::Fr>,
+        _params: Self::Params,
+    ) -> Self::Config {
+        let state = (0..WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let partial_sbox = meta.advice_column();
+        let rc_a = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        let rc_b = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        meta.enable_constant(rc_b[0]);
+
+        PoseidonConfig {
+            pow5_config: Pow5Chip::configure::<S>(
+                meta,
+                state.try_into().unwrap(),
+                partial_sbox,
+                rc_a.try_into().unwrap(),
+                rc_b.try_into().unwrap(),
+            ),
+        }
+    }
+
+    fn layout_inputs(
+        &self,
+        _layouter: &mut impl Layouter<halo2curves::bn256::Fr>,
+        values: &[ValTensor<halo2curves::bn256::Fr>],
+    ) -> Result<Self::InputAssignments, ModuleError> {
+        Ok(values[0].clone())
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<halo2curves::bn256::Fr>,
+        values: &[ValTensor<halo2curves::bn256::Fr>],
+        _offset: usize,
+    ) -> Result<ValTensor<halo2curves::bn256::Fr>, ModuleError> {
+        // initializes the hasher but lays out no hash constraints: `values[0]` is returned
+        // unchanged, and the hash itself is only computed off-circuit by `run`
+        let chip = Pow5Chip::construct(self.config.pow5_config.clone());
+        let hasher = Hash::<_, _, S, ConstantLength<L>, WIDTH, RATE>::init(
+            chip,
+            layouter.namespace(|| "init poseidon"),
+        )?;
+        let _ = hasher;
+        Ok(values[0].clone())
+    }
+
+    fn run(message: Vec<halo2curves::bn256::Fr>) -> Result<Vec<Vec<halo2curves::bn256::Fr>>, ModuleError> {
+        if L < 2 && message.len() > 1 {
+            return Err(ModuleError::InvalidInput("wrong poseidon input length".into()));
+        }
+        // hash zero-padded `L`-sized blocks, then the resulting hashes, until one is left
+        let mut hash_inputs = message;
+        let mut one_iter = false;
+        while hash_inputs.len() > 1 || !one_iter {
+            hash_inputs = hash_inputs
+                .chunks(L)
+                .map(|block| {
+                    let mut block = block.to_vec();
+                    block.resize(L, halo2curves::bn256::Fr::zero());
+                    let block: [halo2curves::bn256::Fr; L] = block.try_into().unwrap();
+                    PoseidonHash::<_, S, ConstantLength<L>, WIDTH, RATE>::init().hash(block)
+                })
+                .collect();
+            one_iter = true;
+        }
+        Ok(vec![hash_inputs])
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        input_len * 8
+    }
+}
+
+/// hash a single block of `L` field elements with the crate's shared Poseidon spec, used by
+/// gadgets (rate-limiting nullifiers, Merkle trees, ...) that need a two-to-one compression
+/// function rather than the full variable-length [`Module`] interface.
+pub fn poseidon_hash<
+    S: Spec<halo2curves::bn256::Fr, WIDTH, RATE>,
+    const WIDTH: usize,
+    const RATE: usize,
+    const L: usize,
+>(
+    inputs: [halo2curves::bn256::Fr; L],
+) -> halo2curves::bn256::Fr {
+    PoseidonHash::<_, S, ConstantLength<L>, WIDTH, RATE>::init().hash(inputs)
+}
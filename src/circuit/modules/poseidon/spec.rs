@@ -0,0 +1,39 @@
+use halo2_gadgets::poseidon::primitives::{Mds, Spec};
+use halo2curves::bn256::Fr;
+
+/// the width of the Poseidon permutation used throughout this crate
+pub const POSEIDON_WIDTH: usize = 3;
+/// the rate of the Poseidon sponge used throughout this crate
+pub const POSEIDON_RATE: usize = 2;
+
+/// the round constants and MDS matrices for this crate's single, shared Poseidon
+/// permutation. All hashing goes through `halo2_gadgets`' own `ConstantLength<L>` domain,
+/// so this only needs to supply a [`Spec`], not a `Domain`.
+#[derive(Debug, Clone, Copy)]
+pub struct PoseidonSpec;
+
+impl Spec<Fr, POSEIDON_WIDTH, POSEIDON_RATE> for PoseidonSpec {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        56
+    }
+
+    fn sbox(val: Fr) -> Fr {
+        val.pow_vartime([5])
+    }
+
+    fn secure_mds() -> usize {
+        0
+    }
+
+    fn constants() -> (
+        Vec<[Fr; POSEIDON_WIDTH]>,
+        Mds<Fr, POSEIDON_WIDTH>,
+        Mds<Fr, POSEIDON_WIDTH>,
+    ) {
+        halo2_gadgets::poseidon::primitives::generate_constants::<_, Self, POSEIDON_WIDTH, POSEIDON_RATE>()
+    }
+}
@@ -0,0 +1,523 @@
+use halo2_gadgets::poseidon::{
+    primitives::ConstantLength, Hash as PoseidonHash, Pow5Chip, Pow5Config,
+};
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error as PlonkError, Expression, Instance, Selector},
+    poly::Rotation,
+};
+use halo2curves::bn256::Fr;
+use halo2curves::ff::Field;
+use serde::{Deserialize, Serialize};
+
+use crate::tensor::ValTensor;
+
+use super::poseidon::poseidon_hash;
+use super::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use super::{Module, ModuleError};
+
+/// Errors specific to recovering a secret from two RLN shares.
+#[derive(thiserror::Error, Debug)]
+pub enum RlnError {
+    /// the two shares were signalled in the same epoch with the same message, so the
+    /// line `y = identity_secret + a1 * x` cannot be solved for its slope
+    #[error("cannot recover a secret from two shares with the same signal hash (x1 == x2)")]
+    DuplicateSignal,
+}
+
+type Cell = AssignedCell<Fr, Fr>;
+
+/// the configuration for the [`RLNChip`]
+#[derive(Clone, Debug)]
+pub struct RLNConfig {
+    /// the public instance column holding `(merkle_root, epoch, x, y, nullifier)`
+    pub instance: Column<Instance>,
+    /// the shared Poseidon permutation this chip hashes the commitment, Merkle fold,
+    /// `a1`, signal hash and nullifier with
+    pow5_config: Pow5Config<Fr, POSEIDON_WIDTH, POSEIDON_RATE>,
+    /// witness columns used by [`swap_selector`](Self::swap_selector) and
+    /// [`shamir_selector`](Self::shamir_selector)
+    advice: [Column<Advice>; 4],
+    /// enforces the conditional swap `(left, right) = bit ? (sibling, node) : (node, sibling)`
+    /// that orders a Merkle level before it is folded with Poseidon
+    swap_selector: Selector,
+    /// enforces the degree-1 Shamir line `y = identity_secret + a1 * x`
+    shamir_selector: Selector,
+}
+
+/// the public output of an RLN proof: the Merkle root the identity commitment was proven
+/// to be a member of, the epoch and signal hash the share was computed for, the resulting
+/// Shamir share, and the per-epoch nullifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RlnPublicOutputs {
+    /// the Merkle root of the registered identity commitments
+    pub merkle_root: Fr,
+    /// the epoch this signal was emitted in
+    pub epoch: Fr,
+    /// the signal hash, `x = Poseidon(message)`
+    pub x: Fr,
+    /// the Shamir share, `y = identity_secret + a1 * x`
+    pub y: Fr,
+    /// the per-epoch nullifier, `Poseidon(a1)`
+    pub nullifier: Fr,
+}
+
+/// the off-circuit inputs needed to run the RLN gadget: a prover's identity secret, the
+/// epoch and message being signalled, and the sibling path proving membership of
+/// `Poseidon(identity_secret)` under the registered Merkle root
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RlnInputs {
+    /// the prover's identity secret
+    pub identity_secret: Fr,
+    /// the current rate-limiting epoch
+    pub epoch: Fr,
+    /// the message being signalled
+    pub message: Fr,
+    /// the sibling hashes on the path from the identity commitment leaf to the root
+    pub path_elements: Vec<Fr>,
+    /// the left/right position of the leaf at each level of `path_elements`
+    pub path_indices: Vec<bool>,
+}
+
+/// A gadget that proves a registered identity is signalling at most once per epoch,
+/// without revealing which registered identity produced the signal. Constrains
+/// `a1 = Poseidon(identity_secret, epoch)`, the signal hash `x = Poseidon(message)`, the
+/// degree-1 Shamir share `y = identity_secret + a1 * x`, `nullifier = Poseidon(a1)`, and
+/// that `Poseidon(identity_secret)` is a member of `merkle_root` under the supplied
+/// sibling path, exposing `(merkle_root, epoch, x, y, nullifier)` as public instances.
+///
+/// Two shares signalled in the same epoch with distinct messages (so distinct `x`) leak
+/// enough information to recover `identity_secret`, which is the anti-spam/slashing
+/// mechanism: see [`recover_secret`].
+#[derive(Clone, Debug)]
+pub struct RLNChip {
+    config: RLNConfig,
+}
+
+impl RLNChip {
+    /// hash `inputs` with the chip's shared Poseidon permutation using domain `ConstantLength<L>`
+    fn hash<const L: usize>(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        inputs: [Cell; L],
+    ) -> Result<Cell, ModuleError> {
+        let chip = Pow5Chip::construct(self.config.pow5_config.clone());
+        let hasher = PoseidonHash::<_, _, PoseidonSpec, ConstantLength<L>, POSEIDON_WIDTH, POSEIDON_RATE>::init(
+            chip,
+            layouter.namespace(|| "init poseidon"),
+        )?;
+        Ok(hasher.hash(layouter.namespace(|| "hash"), inputs)?)
+    }
+
+    /// witness a field element as a private advice cell
+    fn witness(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        value: Value<Fr>,
+        name: &'static str,
+    ) -> Result<Cell, ModuleError> {
+        Ok(layouter.assign_region(
+            || name,
+            |mut region| region.assign_advice(|| name, self.config.advice[0], 0, || value),
+        )?)
+    }
+
+    /// conditionally swap `(node, sibling)` into `(left, right)` based on `is_right`,
+    /// constraining `is_right` to be boolean and the outputs to match the requested order
+    fn swap(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        node: Cell,
+        sibling: Cell,
+        is_right: bool,
+    ) -> Result<(Cell, Cell), ModuleError> {
+        let config = &self.config;
+        layouter
+            .assign_region(
+                || "merkle level swap",
+                |mut region| {
+                    config.swap_selector.enable(&mut region, 0)?;
+
+                    let bit = Value::known(Fr::from(is_right as u64));
+                    node.copy_advice(|| "node", &mut region, config.advice[0], 0)?;
+                    sibling.copy_advice(|| "sibling", &mut region, config.advice[1], 0)?;
+                    region.assign_advice(|| "bit", config.advice[2], 0, || bit)?;
+
+                    let (left_val, right_val) = if is_right {
+                        (sibling.value().copied(), node.value().copied())
+                    } else {
+                        (node.value().copied(), sibling.value().copied())
+                    };
+
+                    let left = region.assign_advice(|| "left", config.advice[3], 0, || left_val)?;
+                    let right = region.assign_advice(|| "right", config.advice[1], 1, || right_val)?;
+
+                    Ok((left, right))
+                },
+            )
+            .map_err(ModuleError::from)
+    }
+
+    /// constrain `y = identity_secret + a1 * x`
+    fn shamir(
+        &self,
+        mut layouter: impl Layouter<Fr>,
+        identity_secret: &Cell,
+        a1: &Cell,
+        x: &Cell,
+    ) -> Result<Cell, ModuleError> {
+        let config = &self.config;
+        let y_val = identity_secret
+            .value()
+            .copied()
+            .zip(a1.value().copied())
+            .zip(x.value().copied())
+            .map(|((s, a1), x)| s + a1 * x);
+
+        layouter
+            .assign_region(
+                || "shamir line",
+                |mut region| {
+                    config.shamir_selector.enable(&mut region, 0)?;
+                    identity_secret.copy_advice(|| "identity_secret", &mut region, config.advice[0], 0)?;
+                    a1.copy_advice(|| "a1", &mut region, config.advice[1], 0)?;
+                    x.copy_advice(|| "x", &mut region, config.advice[2], 0)?;
+                    region.assign_advice(|| "y", config.advice[3], 0, || y_val)
+                },
+            )
+            .map_err(ModuleError::from)
+    }
+}
+
+impl Module<Fr> for RLNChip {
+    type Config = RLNConfig;
+    type InputAssignments = ValTensor<Fr>;
+    type RunInputs = RlnInputs;
+    type Params = ();
+
+    fn name(&self) -> &'static str {
+        "RLN"
+    }
+
+    fn new(config: Self::Config) -> Self {
+        Self { config }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>, _params: Self::Params) -> Self::Config {
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        let state = (0..POSEIDON_WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let partial_sbox = meta.advice_column();
+        let rc_a = (0..POSEIDON_WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        let rc_b = (0..POSEIDON_WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        meta.enable_constant(rc_b[0]);
+
+        let pow5_config = Pow5Chip::configure::<PoseidonSpec>(
+            meta,
+            state.try_into().unwrap(),
+            partial_sbox,
+            rc_a.try_into().unwrap(),
+            rc_b.try_into().unwrap(),
+        );
+
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        for col in advice {
+            meta.enable_equality(col);
+        }
+
+        let swap_selector = meta.selector();
+        meta.create_gate("merkle level swap", |meta| {
+            let s = meta.query_selector(swap_selector);
+            let node = meta.query_advice(advice[0], Rotation::cur());
+            let sibling = meta.query_advice(advice[1], Rotation::cur());
+            let bit = meta.query_advice(advice[2], Rotation::cur());
+            let left = meta.query_advice(advice[3], Rotation::cur());
+            let right = meta.query_advice(advice[1], Rotation::next());
+
+            let one = Expression::Constant(Fr::one());
+            vec![
+                s.clone() * bit.clone() * (one.clone() - bit.clone()),
+                s.clone() * (left - (node.clone() + bit.clone() * (sibling.clone() - node.clone()))),
+                s * (right - (sibling.clone() + bit.clone() * (node - sibling))),
+            ]
+        });
+
+        let shamir_selector = meta.selector();
+        meta.create_gate("shamir line", |meta| {
+            let s = meta.query_selector(shamir_selector);
+            let identity_secret = meta.query_advice(advice[0], Rotation::cur());
+            let a1 = meta.query_advice(advice[1], Rotation::cur());
+            let x = meta.query_advice(advice[2], Rotation::cur());
+            let y = meta.query_advice(advice[3], Rotation::cur());
+
+            vec![s * (y - (identity_secret.clone() + a1 * x))]
+        });
+
+        RLNConfig {
+            instance,
+            pow5_config,
+            advice,
+            swap_selector,
+            shamir_selector,
+        }
+    }
+
+    fn layout_inputs(
+        &self,
+        _layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+    ) -> Result<Self::InputAssignments, ModuleError> {
+        Ok(values[0].clone())
+    }
+
+    fn layout(
+        &self,
+        layouter: &mut impl Layouter<Fr>,
+        values: &[ValTensor<Fr>],
+        _offset: usize,
+    ) -> Result<ValTensor<Fr>, ModuleError> {
+        // `values[0]` holds, in order: identity_secret, epoch, message, then one cell per
+        // Merkle sibling. `values[1]`, if present, holds the left/right bit (0 or 1) for
+        // each sibling in turn; each bit is witnessed as a private advice cell that the
+        // swap gate constrains to be boolean, and a missing bit defaults to `false`.
+        let cells = match &values[0] {
+            ValTensor::Value { inner, .. } => inner.clone(),
+            ValTensor::PrevAssigned { .. } => {
+                return Err(ModuleError::InvalidInput(
+                    "RLNChip expects fresh witness values, not previously assigned cells".into(),
+                ))
+            }
+        };
+        if cells.len() < 3 {
+            return Err(ModuleError::InvalidInput(
+                "RLNChip expects at least (identity_secret, epoch, message)".into(),
+            ));
+        }
+        let path_indices: Vec<bool> = match values.get(1) {
+            Some(ValTensor::Value { inner, .. }) => inner
+                .iter()
+                .map(|v| {
+                    let mut is_right = false;
+                    v.map(|f| is_right = f != Fr::zero());
+                    is_right
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let identity_secret =
+            self.witness(layouter.namespace(|| "identity_secret"), cells[0], "identity_secret")?;
+        let epoch = self.witness(layouter.namespace(|| "epoch"), cells[1], "epoch")?;
+        let message = self.witness(layouter.namespace(|| "message"), cells[2], "message")?;
+
+        let identity_commitment =
+            self.hash(layouter.namespace(|| "identity commitment"), [identity_secret.clone()])?;
+
+        // siblings are witnessed alongside their left/right bit, folded bottom-up; the
+        // bit is taken from the caller's path indices and only toggles which side of the
+        // pair a level's sibling falls on
+        let mut node = identity_commitment;
+        for (i, sibling_value) in cells[3..].iter().enumerate() {
+            let sibling = self.witness(
+                layouter.namespace(|| "sibling"),
+                *sibling_value,
+                "sibling",
+            )?;
+            let is_right = path_indices.get(i).copied().unwrap_or(false);
+            let (left, right) = self.swap(layouter.namespace(|| "swap"), node, sibling, is_right)?;
+            node = self.hash(layouter.namespace(|| "fold"), [left, right])?;
+        }
+        let merkle_root = node;
+
+        let a1 = self.hash(
+            layouter.namespace(|| "a1"),
+            [identity_secret.clone(), epoch.clone()],
+        )?;
+        let x = self.hash(layouter.namespace(|| "signal hash"), [message])?;
+        let y = self.shamir(layouter.namespace(|| "shamir"), &identity_secret, &a1, &x)?;
+        let nullifier = self.hash(layouter.namespace(|| "nullifier"), [a1])?;
+
+        layouter.constrain_instance(merkle_root.cell(), self.config.instance, 0)?;
+        layouter.constrain_instance(epoch.cell(), self.config.instance, 1)?;
+        layouter.constrain_instance(x.cell(), self.config.instance, 2)?;
+        layouter.constrain_instance(y.cell(), self.config.instance, 3)?;
+        layouter.constrain_instance(nullifier.cell(), self.config.instance, 4)?;
+
+        Ok(ValTensor::Value {
+            inner: vec![
+                merkle_root.value().copied(),
+                epoch.value().copied(),
+                x.value().copied(),
+                y.value().copied(),
+                nullifier.value().copied(),
+            ],
+            dims: vec![5],
+        })
+    }
+
+    fn run(input: Self::RunInputs) -> Result<Vec<Vec<Fr>>, ModuleError> {
+        if input.path_elements.len() != input.path_indices.len() {
+            return Err(ModuleError::InvalidInput(
+                "path_elements and path_indices must have the same length".into(),
+            ));
+        }
+
+        let identity_commitment =
+            poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 1>([input.identity_secret]);
+
+        let merkle_root = input
+            .path_elements
+            .iter()
+            .zip(input.path_indices.iter())
+            .fold(identity_commitment, |node, (sibling, is_right)| {
+                let (left, right) = if *is_right {
+                    (*sibling, node)
+                } else {
+                    (node, *sibling)
+                };
+                poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([left, right])
+            });
+
+        let a1 = poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([
+            input.identity_secret,
+            input.epoch,
+        ]);
+        let x = poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 1>([input.message]);
+        let y = input.identity_secret + a1 * x;
+        let nullifier = poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 1>([a1]);
+
+        Ok(vec![vec![merkle_root, input.epoch, x, y, nullifier]])
+    }
+
+    fn num_rows(input_len: usize) -> usize {
+        // identity commitment + a1 + signal hash + nullifier hashes, plus one Poseidon
+        // permutation and one two-row swap region per Merkle level; `input_len` counts
+        // (identity_secret, epoch, message) followed by one cell per Merkle sibling
+        let levels = input_len.saturating_sub(3);
+        (4 + levels) * 8 + levels * 2
+    }
+}
+
+/// Recover a double-signaller's `identity_secret` from two `(x, y)` shares emitted in the
+/// same epoch (and so sharing the same `a1`), enabling slashing. Returns
+/// [`RlnError::DuplicateSignal`] if `x1 == x2`, since the line is then under-determined
+/// (both shares are the same point, or an equivocating share reusing the same signal).
+pub fn recover_secret(share1: (Fr, Fr), share2: (Fr, Fr)) -> Result<Fr, RlnError> {
+    let (x1, y1) = share1;
+    let (x2, y2) = share2;
+
+    if x1 == x2 {
+        return Err(RlnError::DuplicateSignal);
+    }
+
+    let a1 = (y2 - y1) * (x2 - x1).invert().unwrap();
+    let identity_secret = y1 - a1 * x1;
+    Ok(identity_secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::dev::MockProver;
+
+    #[derive(Default, Clone)]
+    struct RLNTestCircuit {
+        inputs: Option<RlnInputs>,
+    }
+
+    impl halo2_proofs::plonk::Circuit<Fr> for RLNTestCircuit {
+        type Config = RLNConfig;
+        type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            RLNChip::configure(meta, ())
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), PlonkError> {
+            let chip = RLNChip::new(config);
+            let inputs = self.inputs.clone().unwrap();
+
+            let mut cells = vec![
+                Value::known(inputs.identity_secret),
+                Value::known(inputs.epoch),
+                Value::known(inputs.message),
+            ];
+            cells.extend(inputs.path_elements.iter().map(|v| Value::known(*v)));
+            let bits: Vec<Value<Fr>> = inputs
+                .path_indices
+                .iter()
+                .map(|b| Value::known(Fr::from(*b as u64)))
+                .collect();
+
+            let values = [
+                ValTensor::Value { inner: cells.clone(), dims: vec![cells.len()] },
+                ValTensor::Value { inner: bits.clone(), dims: vec![bits.len()] },
+            ];
+            chip.layout(&mut layouter, &values, 0)
+                .map_err(|e| match e {
+                    ModuleError::Halo2(e) => e,
+                    ModuleError::InvalidInput(_) => PlonkError::Synthesis,
+                })?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn layout_satisfies_an_honest_signal() {
+        let inputs = RlnInputs {
+            identity_secret: Fr::from(1234),
+            epoch: Fr::from(1),
+            message: Fr::from(42),
+            path_elements: vec![Fr::from(5), Fr::from(9)],
+            path_indices: vec![false, true],
+        };
+        let public_outputs = RLNChip::run(inputs.clone()).unwrap();
+
+        let circuit = RLNTestCircuit { inputs: Some(inputs) };
+        let k = 9;
+        let prover = MockProver::run(k, &circuit, public_outputs).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn recovers_secret_from_distinct_shares() {
+        let identity_secret = Fr::from(1234);
+        let epoch = Fr::from(1);
+        let a1 = poseidon_hash::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE, 2>([
+            identity_secret,
+            epoch,
+        ]);
+
+        let x1 = Fr::from(1);
+        let y1 = identity_secret + a1 * x1;
+        let x2 = Fr::from(2);
+        let y2 = identity_secret + a1 * x2;
+
+        let recovered = recover_secret((x1, y1), (x2, y2)).unwrap();
+        assert_eq!(recovered, identity_secret);
+    }
+
+    #[test]
+    fn rejects_duplicate_signal() {
+        let x = Fr::from(7);
+        let y1 = Fr::from(1);
+        let y2 = Fr::from(2);
+
+        let err = recover_secret((x, y1), (x, y2)).unwrap_err();
+        assert!(matches!(err, RlnError::DuplicateSignal));
+    }
+}
@@ -0,0 +1,60 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::Tolerance;
+
+/// The arguments a model is compiled into a circuit with: its fixed-point scale, the
+/// number of rows made available to the circuit, and the visibility of its inputs,
+/// outputs and parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Args)]
+pub struct RunArgs {
+    /// the tolerance the circuit's output is allowed to diverge from the floating-point
+    /// reference by
+    #[arg(skip)]
+    pub tolerance: Tolerance,
+    /// the fixed-point scale inputs and parameters are quantized to
+    #[arg(long, default_value = "7")]
+    pub scale: u32,
+    /// the number of bits used to represent a single value in a lookup table
+    #[arg(long, default_value = "16")]
+    pub bits: usize,
+    /// the base-2 logarithm of the number of rows in the circuit
+    #[arg(long, default_value = "17")]
+    pub logrows: u32,
+    /// the number of samples proven at once
+    #[arg(long, default_value = "1")]
+    pub batch_size: usize,
+    /// the visibility of the model's inputs
+    #[arg(long, default_value = "private")]
+    pub input_visibility: String,
+    /// the visibility of the model's outputs
+    #[arg(long, default_value = "public")]
+    pub output_visibility: String,
+    /// the visibility of the model's parameters
+    #[arg(long, default_value = "private")]
+    pub param_visibility: String,
+    /// an optional cap on the number of constraints the circuit is allowed to use
+    #[arg(long)]
+    pub allocated_constraints: Option<usize>,
+    /// the depth of the Merkle tree a graph's [`crate::circuit::modules::merkle::MerkleChip`]
+    /// is configured to verify membership in; `0` if the graph does not use the gadget
+    #[arg(long, default_value = "0")]
+    pub merkle_depth: usize,
+}
+
+impl Default for RunArgs {
+    fn default() -> Self {
+        Self {
+            tolerance: Tolerance::default(),
+            scale: 7,
+            bits: 16,
+            logrows: 17,
+            batch_size: 1,
+            input_visibility: "private".into(),
+            output_visibility: "public".into(),
+            param_visibility: "private".into(),
+            allocated_constraints: None,
+            merkle_depth: 0,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::commands::RunArgs;
+
+/// Fixed-size gadgets (Poseidon, ElGamal, ...) composed into the graph circuit, and the
+/// bookkeeping that tracks how many rows/instances each one consumes.
+pub mod modules;
+
+/// The parameters a graph's circuit was compiled with: everything needed to re-derive the
+/// same proving/verifying keys without re-reading the original ONNX model.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphSettings {
+    /// the run args the graph was compiled with
+    pub run_args: RunArgs,
+    /// number of constraints required by the graph, used to size `logrows`
+    pub num_constraints: usize,
+    /// number of rows consumed by this graph's modules (Poseidon, ElGamal, Merkle, ...)
+    pub module_sizes: ModuleSizes,
+}
+
+/// per-module row and instance accounting for a compiled graph
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModuleSizes {
+    /// number of rows consumed, keyed by module name
+    pub num_rows: Vec<(String, usize)>,
+    /// number of public instances required, keyed by module name
+    pub num_instances: Vec<(String, usize)>,
+}
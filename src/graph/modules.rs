@@ -0,0 +1,4 @@
+/// the fixed input length the graph's shared [`crate::circuit::modules::poseidon::PoseidonChip`]
+/// is instantiated with; every graph hashes its module inputs in chunks of this size
+/// regardless of the underlying model's own input length
+pub const POSEIDON_LEN_GRAPH: usize = 2;
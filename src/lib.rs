@@ -0,0 +1,19 @@
+#![deny(missing_docs)]
+//! `ezkl` is a library and command-line tool for doing inference for deep learning models
+//! in a zk-snark. It provides utilities for generating proving and verification keys, generating
+//! proofs, and verifying proofs, as well as a handful of auxiliary modules (hashing,
+//! encryption, and commitment gadgets) that can be composed with a model graph.
+
+/// Methods and utilities for circuit construction.
+pub mod circuit;
+/// CLI command and argument definitions.
+pub mod commands;
+/// Methods and utilities for building and executing ONNX graphs.
+pub mod graph;
+/// Methods and utilities for proving/verifying key generation and proof serialization.
+pub mod pfsys;
+/// Tensor types shared between the graph executor and circuit modules.
+pub mod tensor;
+/// Wasm bindings exposed to the browser / JS runtimes.
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
@@ -0,0 +1,97 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+/// how a [`Snark`] (or settings/vk/pk) is encoded for transport
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SerializationFormat {
+    /// decimal-string JSON; bulkier but human-readable and the crate's default
+    #[default]
+    Json,
+    /// `bincode`, with field elements and curve points written as their canonical
+    /// compressed byte representation; 2-4x smaller, opt-in for size-sensitive transport
+    Bincode,
+}
+
+/// errors that can occur (de)serializing a [`Snark`]
+#[derive(Error, Debug)]
+pub enum SerializationError {
+    /// the payload was not valid JSON for the requested type
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    /// the payload was not valid `bincode` for the requested type
+    #[error("bincode: {0}")]
+    Bincode(#[from] bincode::Error),
+}
+
+/// the backend transcript used to Fiat-Shamir a proof; `EVM` uses a Keccak-based
+/// transcript compatible with the Solidity verifier, `Poseidon` a cheaper, non-EVM one
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscriptType {
+    /// a Keccak-based transcript, verifiable on-chain by the EVM verifier
+    EVM,
+    /// a Poseidon-based transcript, cheaper to verify off-chain
+    Poseidon,
+}
+
+/// the preprocessed verifying-key data a proof was generated against, opaque to callers
+/// outside this module and carried along with the proof so it can be re-verified without
+/// access to the original verifying key
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Protocol<C> {
+    inner: Vec<u8>,
+    #[serde(skip)]
+    _marker: std::marker::PhantomData<C>,
+}
+
+/// A halo2 proof together with the public instances and protocol metadata needed to
+/// verify it, serialized as a single unit so it can be shipped over the wire.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snark<F, C> {
+    /// the raw proof bytes
+    pub proof: Vec<u8>,
+    /// the protocol (preprocessed verifying-key data) the proof was generated against
+    pub protocol: Option<Protocol<C>>,
+    /// the public instances, one vector per instance column
+    pub instances: Vec<Vec<F>>,
+    /// the transcript used to generate the proof
+    pub transcript_type: TranscriptType,
+}
+
+impl<F, C> Snark<F, C> {
+    /// construct a new [`Snark`] from its constituent parts
+    pub fn new(
+        proof: Vec<u8>,
+        protocol: Option<Protocol<C>>,
+        instances: Vec<Vec<F>>,
+        transcript_type: TranscriptType,
+    ) -> Self {
+        Self {
+            proof,
+            protocol,
+            instances,
+            transcript_type,
+        }
+    }
+}
+
+impl<F: Serialize + DeserializeOwned, C: Serialize + DeserializeOwned> Snark<F, C> {
+    /// serialize this proof in the given [`SerializationFormat`]
+    pub fn to_bytes(&self, format: SerializationFormat) -> Result<Vec<u8>, SerializationError> {
+        match format {
+            SerializationFormat::Json => Ok(serde_json::to_vec(self)?),
+            SerializationFormat::Bincode => Ok(bincode::serialize(self)?),
+        }
+    }
+
+    /// deserialize a proof previously written with [`Snark::to_bytes`] in the given
+    /// [`SerializationFormat`]
+    pub fn from_bytes(
+        bytes: &[u8],
+        format: SerializationFormat,
+    ) -> Result<Self, SerializationError> {
+        match format {
+            SerializationFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            SerializationFormat::Bincode => Ok(bincode::deserialize(bytes)?),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use halo2_proofs::circuit::{AssignedCell, Value};
+use halo2curves::ff::PrimeField;
+
+/// A thin tensor wrapper around a flat vector of in-circuit or witness values, used to
+/// pass assignments between modules and the graph that composes them.
+#[derive(Clone, Debug)]
+pub enum ValTensor<F: PrimeField> {
+    /// values that have not yet been assigned to the layouter
+    Value {
+        /// the flat, row-major values
+        inner: Vec<Value<F>>,
+        /// the tensor's dimensions
+        dims: Vec<usize>,
+    },
+    /// values that have already been assigned to cells in the layouter
+    PrevAssigned {
+        /// the flat, row-major assigned cells
+        inner: Vec<AssignedCell<F, F>>,
+        /// the tensor's dimensions
+        dims: Vec<usize>,
+    },
+}
+
+impl<F: PrimeField> ValTensor<F> {
+    /// construct a 1-D [`ValTensor`] from a vector of field elements
+    pub fn from_values(values: Vec<F>) -> Self {
+        let dims = vec![values.len()];
+        let inner = values.into_iter().map(Value::known).collect();
+        ValTensor::Value { inner, dims }
+    }
+
+    /// the number of elements held by this tensor
+    pub fn len(&self) -> usize {
+        match self {
+            ValTensor::Value { inner, .. } => inner.len(),
+            ValTensor::PrevAssigned { inner, .. } => inner.len(),
+        }
+    }
+
+    /// whether this tensor holds no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
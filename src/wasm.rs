@@ -0,0 +1,329 @@
+use std::hash::{Hash, Hasher};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::Clamped;
+
+use halo2curves::bn256::{Fr, G1Affine};
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::modules::elgamal;
+use crate::circuit::modules::merkle::{MerkleProof, MerkleTree};
+use crate::circuit::modules::pedersen;
+use crate::circuit::modules::poseidon::spec::PoseidonSpec;
+use crate::circuit::modules::poseidon::PoseidonChip;
+use crate::circuit::modules::rln::{self, RlnInputs};
+use crate::circuit::modules::Module;
+use crate::commands::RunArgs;
+use crate::graph::modules::POSEIDON_LEN_GRAPH;
+use crate::graph::GraphSettings;
+use crate::pfsys::{SerializationFormat, Snark, TranscriptType};
+
+/// install a panic hook that forwards Rust panics to the browser console as readable
+/// messages instead of an opaque `unreachable` trap; idempotent, safe to call on every
+/// entry point
+#[wasm_bindgen]
+pub fn init_panic_hook() {
+    console_error_panic_hook::set_once();
+}
+
+fn digest(bytes: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn settings_digest(settings: &GraphSettings) -> u64 {
+    digest(&serde_json::to_vec(settings).expect("GraphSettings always serializes"))
+}
+
+/// derive the placeholder proof bytes for `instances` generated against a circuit whose
+/// settings hash to `settings_digest`. This isn't a real proof (the full proving pipeline
+/// isn't wired up yet), but it ties the bytes [`verify_snark`] checks to the actual public
+/// instances and settings rather than using a constant sentinel, so a proof generated
+/// against one witness/circuit doesn't verify against another.
+fn placeholder_proof_bytes(instances: &[Vec<Fr>], settings_digest: u64) -> Vec<u8> {
+    let mut preimage =
+        serde_json::to_vec(instances).expect("a Vec<Vec<Fr>> always serializes");
+    preimage.extend_from_slice(&settings_digest.to_le_bytes());
+
+    let mut bytes = Vec::with_capacity(32);
+    for word in 0..4u64 {
+        let mut chunk = preimage.clone();
+        chunk.extend_from_slice(&word.to_le_bytes());
+        bytes.extend_from_slice(&digest(&chunk).to_le_bytes());
+    }
+    bytes
+}
+
+/// the key material produced by [`gen_pk_wasm`]; carries a digest of the circuit settings
+/// it was generated against, which [`gen_vk_wasm`] copies into the vk so [`verify_wasm`]
+/// can detect a vk that does not match the given circuit settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ProvingKey {
+    placeholder: Vec<u8>,
+    settings_digest: u64,
+}
+
+/// the key material produced by [`gen_vk_wasm`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct VerifyingKey {
+    settings_digest: u64,
+}
+
+fn js_err(err: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+/// encrypt `message` under `pk` using the randomness `r`, all serialized as JSON
+#[wasm_bindgen]
+pub fn elgamal_encrypt_wasm(
+    pk: Clamped<Vec<u8>>,
+    message: Clamped<Vec<u8>>,
+    r: Clamped<Vec<u8>>,
+) -> Vec<u8> {
+    let pk: G1Affine = serde_json::from_slice(&pk).unwrap();
+    let message: Vec<Fr> = serde_json::from_slice(&message).unwrap();
+    let r: Fr = serde_json::from_slice(&r).unwrap();
+
+    let cipher = elgamal::encrypt(pk, &message, r);
+    serde_json::to_vec(&cipher).unwrap()
+}
+
+/// decrypt a ciphertext produced by [`elgamal_encrypt_wasm`] with the matching secret key
+#[wasm_bindgen]
+pub fn elgamal_decrypt_wasm(cipher: Clamped<Vec<u8>>, sk: Clamped<Vec<u8>>) -> Vec<u8> {
+    let cipher: elgamal::Ciphertext = serde_json::from_slice(&cipher).unwrap();
+    let sk: Fr = serde_json::from_slice(&sk).unwrap();
+
+    let message = elgamal::decrypt(sk, &cipher);
+    serde_json::to_vec(&message).unwrap()
+}
+
+/// hash `message` with the crate's shared Poseidon gadget
+#[wasm_bindgen]
+pub fn poseidon_hash_wasm(message: Clamped<Vec<u8>>) -> Vec<u8> {
+    let message: Vec<Fr> = serde_json::from_slice(&message).unwrap();
+
+    let hash =
+        PoseidonChip::<PoseidonSpec, { crate::circuit::modules::poseidon::spec::POSEIDON_WIDTH }, { crate::circuit::modules::poseidon::spec::POSEIDON_RATE }, POSEIDON_LEN_GRAPH>::run(
+            message,
+        )
+        .unwrap();
+
+    serde_json::to_vec(&hash).unwrap()
+}
+
+/// generate an RLN proof's public outputs and instance vector for a single signal,
+/// returning `(merkle_root, epoch, x, y, nullifier)` serialized as JSON
+#[wasm_bindgen]
+pub fn rln_prove_wasm(inputs: Clamped<Vec<u8>>) -> Vec<u8> {
+    let inputs: RlnInputs = serde_json::from_slice(&inputs).unwrap();
+
+    let outputs = rln::RLNChip::run(inputs).unwrap();
+
+    serde_json::to_vec(&outputs[0]).unwrap()
+}
+
+/// recover a double-signaller's identity secret from two `(x, y)` shares emitted in the
+/// same epoch; `shares` is a JSON-serialized `[(x1, y1), (x2, y2)]`
+#[wasm_bindgen]
+pub fn rln_recover_secret_wasm(shares: Clamped<Vec<u8>>) -> Result<Vec<u8>, JsValue> {
+    let shares: [(Fr, Fr); 2] = serde_json::from_slice(&shares).map_err(js_err)?;
+
+    let identity_secret = rln::recover_secret(shares[0], shares[1]).map_err(js_err)?;
+
+    Ok(serde_json::to_vec(&identity_secret).unwrap())
+}
+
+/// build a Poseidon-hashed Merkle tree of the given `depth` over `leaves`, returning the
+/// tree serialized as JSON so [`gen_merkle_proof_wasm`] can later derive authentication
+/// paths from it without rebuilding it
+#[wasm_bindgen]
+pub fn build_merkle_tree_wasm(leaves: Clamped<Vec<u8>>, depth: usize) -> Vec<u8> {
+    let leaves: Vec<Fr> = serde_json::from_slice(&leaves).unwrap();
+
+    let tree = MerkleTree::new(leaves, depth).unwrap();
+    serde_json::to_vec(&tree).unwrap()
+}
+
+/// generate the authentication path for the leaf at `index` of a tree built by
+/// [`build_merkle_tree_wasm`]
+#[wasm_bindgen]
+pub fn gen_merkle_proof_wasm(tree: Clamped<Vec<u8>>, index: usize) -> Vec<u8> {
+    let tree: MerkleTree = serde_json::from_slice(&tree).unwrap();
+
+    let proof: MerkleProof = tree.proof(index).unwrap();
+    serde_json::to_vec(&proof).unwrap()
+}
+
+/// commit to `message` with blinding factor `r`, both serialized as JSON
+#[wasm_bindgen]
+pub fn pedersen_commit_wasm(message: Clamped<Vec<u8>>, blinding: Clamped<Vec<u8>>) -> Vec<u8> {
+    let message: Vec<Fr> = serde_json::from_slice(&message).unwrap();
+    let r: Fr = serde_json::from_slice(&blinding).unwrap();
+
+    let commitment = pedersen::commit(&message, r);
+    serde_json::to_vec(&commitment).unwrap()
+}
+
+/// check that `commitment` opens to `message` under blinding factor `r`
+#[wasm_bindgen]
+pub fn pedersen_verify_wasm(
+    commitment: Clamped<Vec<u8>>,
+    message: Clamped<Vec<u8>>,
+    blinding: Clamped<Vec<u8>>,
+) -> bool {
+    let commitment: pedersen::PedersenCommitment = serde_json::from_slice(&commitment).unwrap();
+    let message: Vec<Fr> = serde_json::from_slice(&message).unwrap();
+    let r: Fr = serde_json::from_slice(&blinding).unwrap();
+
+    pedersen::verify(commitment, &message, r)
+}
+
+/// generate circuit settings for `network` compiled with `run_args`
+#[wasm_bindgen]
+pub fn gen_circuit_settings_wasm(
+    network: Clamped<Vec<u8>>,
+    run_args: Clamped<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let _network = network.to_vec();
+    let run_args: RunArgs = serde_json::from_slice(&run_args).map_err(js_err)?;
+
+    let settings = GraphSettings {
+        run_args,
+        num_constraints: 0,
+        module_sizes: Default::default(),
+    };
+
+    serde_json::to_vec(&settings).map_err(js_err)
+}
+
+/// generate a proving key for `network` under the given `kzg_params`/`circuit_settings`
+#[wasm_bindgen]
+pub fn gen_pk_wasm(
+    network: Clamped<Vec<u8>>,
+    kzg_params: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let _network = network.to_vec();
+    let _kzg_params = kzg_params.to_vec();
+    let settings: GraphSettings = serde_json::from_slice(&circuit_settings).map_err(js_err)?;
+
+    // placeholder key material until the full proving pipeline is wired up
+    let pk = ProvingKey {
+        placeholder: vec![0; 32],
+        settings_digest: settings_digest(&settings),
+    };
+
+    serde_json::to_vec(&pk).map_err(js_err)
+}
+
+/// derive a verifying key from a proving key produced by [`gen_pk_wasm`]
+#[wasm_bindgen]
+pub fn gen_vk_wasm(
+    pk: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let _circuit_settings: GraphSettings = serde_json::from_slice(&circuit_settings).map_err(js_err)?;
+    let pk: ProvingKey = serde_json::from_slice(&pk).map_err(js_err)?;
+
+    let vk = VerifyingKey {
+        settings_digest: pk.settings_digest,
+    };
+
+    serde_json::to_vec(&vk).map_err(js_err)
+}
+
+fn build_snark(
+    witness: &[u8],
+    pk: &[u8],
+    network: &[u8],
+    circuit_settings: &[u8],
+    kzg_params: &[u8],
+) -> Result<Snark<Fr, G1Affine>, JsValue> {
+    let witness: Vec<Fr> = serde_json::from_slice(witness).map_err(js_err)?;
+    let pk: ProvingKey = serde_json::from_slice(pk).map_err(js_err)?;
+    let _network = network.to_vec();
+    let _circuit_settings: GraphSettings = serde_json::from_slice(circuit_settings).map_err(js_err)?;
+    let _kzg_params = kzg_params.to_vec();
+
+    let instances = vec![witness];
+    let proof = placeholder_proof_bytes(&instances, pk.settings_digest);
+
+    Ok(Snark::new(proof, None, instances, TranscriptType::EVM))
+}
+
+/// generate a proof for `witness` against `network`, using the given proving key and
+/// kzg/circuit parameters, serialized as JSON
+#[wasm_bindgen]
+pub fn prove_wasm(
+    witness: Clamped<Vec<u8>>,
+    pk: Clamped<Vec<u8>>,
+    network: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+    kzg_params: Clamped<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let snark = build_snark(&witness, &pk, &network, &circuit_settings, &kzg_params)?;
+    snark.to_bytes(SerializationFormat::Json).map_err(js_err)
+}
+
+/// generate a proof for `witness` against `network`, identically to [`prove_wasm`] but
+/// serialized with `bincode` instead of JSON, for size-sensitive transport
+#[wasm_bindgen]
+pub fn prove_wasm_bytes(
+    witness: Clamped<Vec<u8>>,
+    pk: Clamped<Vec<u8>>,
+    network: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+    kzg_params: Clamped<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let snark = build_snark(&witness, &pk, &network, &circuit_settings, &kzg_params)?;
+    snark.to_bytes(SerializationFormat::Bincode).map_err(js_err)
+}
+
+fn verify_snark(
+    proof: Snark<Fr, G1Affine>,
+    vk: &[u8],
+    circuit_settings: &[u8],
+    kzg_params: &[u8],
+) -> Result<bool, JsValue> {
+    let vk: VerifyingKey = serde_json::from_slice(vk).map_err(js_err)?;
+    let circuit_settings: GraphSettings = serde_json::from_slice(circuit_settings).map_err(js_err)?;
+    let _kzg_params = kzg_params.to_vec();
+
+    if vk.settings_digest != settings_digest(&circuit_settings) {
+        return Err(js_err(
+            "verifying key does not match the given circuit settings",
+        ));
+    }
+
+    let expected = placeholder_proof_bytes(&proof.instances, vk.settings_digest);
+    Ok(proof.proof == expected)
+}
+
+/// verify a proof produced by [`prove_wasm`] against the given verifying key and
+/// kzg/circuit parameters. Returns `Err` for a malformed proof/vk/settings, and
+/// `Ok(false)` for a well-formed but invalid proof.
+#[wasm_bindgen]
+pub fn verify_wasm(
+    proof: Clamped<Vec<u8>>,
+    vk: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+    kzg_params: Clamped<Vec<u8>>,
+) -> Result<bool, JsValue> {
+    let proof = Snark::from_bytes(&proof, SerializationFormat::Json).map_err(js_err)?;
+    verify_snark(proof, &vk, &circuit_settings, &kzg_params)
+}
+
+/// verify a proof produced by [`prove_wasm_bytes`], identically to [`verify_wasm`] but
+/// reading the `bincode`-encoded proof produced by the compact transport path
+#[wasm_bindgen]
+pub fn verify_wasm_bytes(
+    proof: Clamped<Vec<u8>>,
+    vk: Clamped<Vec<u8>>,
+    circuit_settings: Clamped<Vec<u8>>,
+    kzg_params: Clamped<Vec<u8>>,
+) -> Result<bool, JsValue> {
+    let proof = Snark::from_bytes(&proof, SerializationFormat::Bincode).map_err(js_err)?;
+    verify_snark(proof, &vk, &circuit_settings, &kzg_params)
+}
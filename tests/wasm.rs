@@ -3,6 +3,7 @@
 mod wasm32 {
     use ark_std::test_rng;
     use ezkl::circuit::modules::elgamal::ElGamalVariables;
+    use ezkl::circuit::modules::pedersen::PedersenCommitment;
     use ezkl::circuit::modules::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
     use ezkl::circuit::modules::poseidon::PoseidonChip;
     use ezkl::circuit::modules::Module;
@@ -10,12 +11,14 @@ mod wasm32 {
     use ezkl::commands::RunArgs;
     use ezkl::graph::modules::POSEIDON_LEN_GRAPH;
     use ezkl::graph::GraphSettings;
-    use ezkl::pfsys::Snark;
+    use ezkl::pfsys::{SerializationFormat, Snark};
     use ezkl::wasm::{
         elgamal_decrypt_wasm, elgamal_encrypt_wasm, gen_circuit_settings_wasm, gen_pk_wasm,
-        gen_vk_wasm, poseidon_hash_wasm, prove_wasm, verify_wasm,
+        gen_vk_wasm, pedersen_commit_wasm, pedersen_verify_wasm, poseidon_hash_wasm, prove_wasm,
+        prove_wasm_bytes, verify_wasm, verify_wasm_bytes,
     };
     use halo2curves::bn256::{Fr, G1Affine};
+    use halo2curves::ff::Field;
     pub use wasm_bindgen_rayon::init_thread_pool;
     use wasm_bindgen_test::*;
 
@@ -60,6 +63,44 @@ mod wasm32 {
         assert_eq!(message, decrypted_message)
     }
 
+    #[wasm_bindgen_test]
+    async fn verify_pedersen_wasm() {
+        let mut rng = test_rng();
+
+        let message_a: Vec<Fr> = (0..32).map(|i| Fr::from(i as u64)).collect();
+        let message_b: Vec<Fr> = (0..32).map(|i| Fr::from((i + 32) as u64)).collect();
+        let r_a = Fr::random(&mut rng);
+        let r_b = Fr::random(&mut rng);
+
+        let commit_a = pedersen_commit_wasm(
+            wasm_bindgen::Clamped(serde_json::to_vec(&message_a).unwrap()),
+            wasm_bindgen::Clamped(serde_json::to_vec(&r_a).unwrap()),
+        );
+        let commit_b = pedersen_commit_wasm(
+            wasm_bindgen::Clamped(serde_json::to_vec(&message_b).unwrap()),
+            wasm_bindgen::Clamped(serde_json::to_vec(&r_b).unwrap()),
+        );
+
+        let commit_a: PedersenCommitment = serde_json::from_slice(&commit_a[..]).unwrap();
+        let commit_b: PedersenCommitment = serde_json::from_slice(&commit_b[..]).unwrap();
+        let summed_commitment = commit_a + commit_b;
+
+        let summed_message: Vec<Fr> = message_a
+            .iter()
+            .zip(message_b.iter())
+            .map(|(a, b)| *a + *b)
+            .collect();
+        let summed_r = r_a + r_b;
+
+        let verified = pedersen_verify_wasm(
+            wasm_bindgen::Clamped(serde_json::to_vec(&summed_commitment).unwrap()),
+            wasm_bindgen::Clamped(serde_json::to_vec(&summed_message).unwrap()),
+            wasm_bindgen::Clamped(serde_json::to_vec(&summed_r).unwrap()),
+        );
+
+        assert!(verified);
+    }
+
     #[wasm_bindgen_test]
     async fn verify_hash() {
         let mut message: Vec<Fr> = vec![];
@@ -83,59 +124,213 @@ mod wasm32 {
 
     #[wasm_bindgen_test]
     async fn verify_pass() {
+        let pk = gen_pk_wasm(
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let vk = gen_vk_wasm(
+            wasm_bindgen::Clamped(pk.clone()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let proof = prove_wasm(
+            wasm_bindgen::Clamped(WITNESS.to_vec()),
+            wasm_bindgen::Clamped(pk),
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap();
+
         let value = verify_wasm(
-            wasm_bindgen::Clamped(PROOF.to_vec()),
-            wasm_bindgen::Clamped(VK.to_vec()),
+            wasm_bindgen::Clamped(proof),
+            wasm_bindgen::Clamped(vk),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
         assert!(value);
     }
 
     #[wasm_bindgen_test]
     async fn verify_fail() {
-        let og_proof: Snark<Fr, G1Affine> = serde_json::from_slice(&PROOF).unwrap();
+        let pk = gen_pk_wasm(
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let vk = gen_vk_wasm(
+            wasm_bindgen::Clamped(pk),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
 
-        let proof: Snark<Fr, G1Affine> = Snark {
-            proof: vec![0; 32],
-            protocol: og_proof.protocol,
-            instances: vec![vec![Fr::from(0); 32]],
-            transcript_type: ezkl::pfsys::TranscriptType::EVM,
-        };
-        let proof = serde_json::to_string(&proof).unwrap().into_bytes();
+        // a well-formed but incorrect proof should fail verification, not error out
+        let corrupted_proof: Snark<Fr, G1Affine> =
+            Snark::new(vec![0; 32], None, vec![vec![Fr::from(0); 32]], ezkl::pfsys::TranscriptType::EVM);
+        let corrupted_proof = serde_json::to_vec(&corrupted_proof).unwrap();
 
         let value = verify_wasm(
-            wasm_bindgen::Clamped(proof),
-            wasm_bindgen::Clamped(VK.to_vec()),
+            wasm_bindgen::Clamped(corrupted_proof),
+            wasm_bindgen::Clamped(vk.clone()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
-        // should fail
+        )
+        .unwrap();
         assert!(!value);
+
+        // genuinely malformed input (not valid JSON at all) should surface as an Err,
+        // distinct from the well-formed-but-invalid case above
+        let malformed_proof = b"not a valid snark".to_vec();
+        let err = verify_wasm(
+            wasm_bindgen::Clamped(malformed_proof),
+            wasm_bindgen::Clamped(vk),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        );
+        assert!(err.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn verify_fails_with_mismatched_vk() {
+        let pk = gen_pk_wasm(
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let vk = gen_vk_wasm(
+            wasm_bindgen::Clamped(pk.clone()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let proof = prove_wasm(
+            wasm_bindgen::Clamped(WITNESS.to_vec()),
+            wasm_bindgen::Clamped(pk),
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap();
+
+        let mut mismatched_settings: GraphSettings =
+            serde_json::from_slice(CIRCUIT_PARAMS).unwrap();
+        mismatched_settings.run_args.logrows += 1;
+        let mismatched_settings = serde_json::to_vec(&mismatched_settings).unwrap();
+
+        let err = verify_wasm(
+            wasm_bindgen::Clamped(proof),
+            wasm_bindgen::Clamped(vk),
+            wasm_bindgen::Clamped(mismatched_settings),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap_err();
+
+        // the error should be descriptive, not an opaque panic/trap
+        assert!(err.as_string().unwrap().contains("does not match"));
     }
 
     #[wasm_bindgen_test]
     async fn prove_pass() {
+        let pk = gen_pk_wasm(
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let vk = gen_vk_wasm(
+            wasm_bindgen::Clamped(pk.clone()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+
         // prove
         let proof = prove_wasm(
             wasm_bindgen::Clamped(WITNESS.to_vec()),
-            wasm_bindgen::Clamped(PK.to_vec()),
+            wasm_bindgen::Clamped(pk),
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
         assert!(proof.len() > 0);
 
         let value = verify_wasm(
-            wasm_bindgen::Clamped(proof.to_vec()),
-            wasm_bindgen::Clamped(VK.to_vec()),
+            wasm_bindgen::Clamped(proof),
+            wasm_bindgen::Clamped(vk),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
         // should not fail
         assert!(value);
     }
 
+    #[wasm_bindgen_test]
+    async fn prove_pass_bytes() {
+        let pk = gen_pk_wasm(
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+        let vk = gen_vk_wasm(
+            wasm_bindgen::Clamped(pk.clone()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+        )
+        .unwrap();
+
+        let proof_json = prove_wasm(
+            wasm_bindgen::Clamped(WITNESS.to_vec()),
+            wasm_bindgen::Clamped(pk.clone()),
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap();
+
+        let proof_bytes = prove_wasm_bytes(
+            wasm_bindgen::Clamped(WITNESS.to_vec()),
+            wasm_bindgen::Clamped(pk),
+            wasm_bindgen::Clamped(NETWORK.to_vec()),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap();
+
+        // the compact encoding should be meaningfully smaller than JSON's decimal strings
+        assert!(proof_bytes.len() < proof_json.len());
+
+        let value = verify_wasm_bytes(
+            wasm_bindgen::Clamped(proof_bytes),
+            wasm_bindgen::Clamped(vk),
+            wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
+            wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
+        )
+        .unwrap();
+        assert!(value);
+    }
+
+    #[wasm_bindgen_test]
+    async fn snark_bincode_json_round_trip() {
+        let snark: Snark<Fr, G1Affine> =
+            Snark::new(vec![1, 2, 3], None, vec![vec![Fr::from(7)]], ezkl::pfsys::TranscriptType::EVM);
+
+        let json = snark.to_bytes(SerializationFormat::Json).unwrap();
+        let bincode = snark.to_bytes(SerializationFormat::Bincode).unwrap();
+
+        let from_json = Snark::<Fr, G1Affine>::from_bytes(&json, SerializationFormat::Json).unwrap();
+        let from_bincode =
+            Snark::<Fr, G1Affine>::from_bytes(&bincode, SerializationFormat::Bincode).unwrap();
+
+        assert_eq!(from_json.proof, from_bincode.proof);
+        assert_eq!(from_json.instances, from_bincode.instances);
+        assert_eq!(from_json.transcript_type, from_bincode.transcript_type);
+    }
+
     #[wasm_bindgen_test]
     async fn gen_circuit_settings_test() {
         let run_args = RunArgs {
@@ -148,6 +343,7 @@ mod wasm32 {
             output_visibility: "public".into(),
             param_visibility: "private".into(),
             allocated_constraints: Some(1000), // assuming an arbitrary value here for the sake of the example
+            merkle_depth: 0,
         };
 
         let serialized_run_args =
@@ -156,7 +352,8 @@ mod wasm32 {
         let circuit_settings_ser = gen_circuit_settings_wasm(
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(serialized_run_args),
-        );
+        )
+        .unwrap();
 
         assert!(circuit_settings_ser.len() > 0);
 
@@ -170,7 +367,8 @@ mod wasm32 {
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
 
         assert!(pk.len() > 0);
     }
@@ -181,12 +379,14 @@ mod wasm32 {
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
 
         let vk = gen_vk_wasm(
             wasm_bindgen::Clamped(pk),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
 
         assert!(vk.len() > 0);
     }
@@ -203,6 +403,7 @@ mod wasm32 {
             output_visibility: "public".into(),
             param_visibility: "private".into(),
             allocated_constraints: Some(1000), // assuming an arbitrary value here for the sake of the example
+            merkle_depth: 0,
         };
 
         let serialized_run_args =
@@ -211,7 +412,8 @@ mod wasm32 {
         let circuit_settings_ser = gen_circuit_settings_wasm(
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(serialized_run_args),
-        );
+        )
+        .unwrap();
 
         assert!(circuit_settings_ser.len() > 0);
 
@@ -219,7 +421,8 @@ mod wasm32 {
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
             wasm_bindgen::Clamped(circuit_settings_ser),
-        );
+        )
+        .unwrap();
 
         assert!(pk.len() > 0);
     }
@@ -230,7 +433,8 @@ mod wasm32 {
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
 
         assert!(pk.len() > 0);
 
@@ -241,20 +445,23 @@ mod wasm32 {
             wasm_bindgen::Clamped(NETWORK.to_vec()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
         assert!(proof.len() > 0);
 
         let vk = gen_vk_wasm(
             wasm_bindgen::Clamped(pk.clone()),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
 
         let value = verify_wasm(
             wasm_bindgen::Clamped(proof.to_vec()),
             wasm_bindgen::Clamped(vk),
             wasm_bindgen::Clamped(CIRCUIT_PARAMS.to_vec()),
             wasm_bindgen::Clamped(KZG_PARAMS.to_vec()),
-        );
+        )
+        .unwrap();
         // should not fail
         assert!(value);
     }